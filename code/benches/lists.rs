@@ -0,0 +1,414 @@
+// How the lists stack up against std's collections, without pulling in a
+// benchmarking crate:
+//
+//     cargo bench --bench lists
+//     cargo bench --bench lists -- --max 10000 --csv > lists.csv
+//
+// For every collection and size it times push, pop, iterating and peeking
+// (in nanoseconds per element) and records the peak heap use while the
+// collection is built, in bytes per element. Lists without a borrowing
+// iterator show `-` for iteration. The numbers are for comparing the
+// collections with each other on one machine, nothing more.
+use code::traits::{Queue, Stack};
+use code::{fifth, first, fourth, second, sixth, third};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::{LinkedList, VecDeque};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use std::time::{Duration, Instant};
+
+// Keeps track of the bytes in use and the most that were in use at once.
+struct PeakAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let now = CURRENT.fetch_add(layout.size(), Relaxed) + layout.size();
+            PEAK.fetch_max(now, Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            let now = CURRENT.fetch_add(new_size, Relaxed) + new_size;
+            PEAK.fetch_max(now, Relaxed);
+            CURRENT.fetch_sub(layout.size(), Relaxed);
+        }
+        new
+    }
+}
+
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc;
+
+// The operations being measured, each collection at whichever end it works
+// best: stacks at the top, queues in at the back and out at the front.
+trait Subject {
+    const NAME: &'static str;
+
+    fn new() -> Self;
+    fn push(&mut self, x: u64);
+    fn pop(&mut self) -> Option<u64>;
+    fn peek(&self) -> Option<u64>;
+    // None if there's no way to look at every element without taking the
+    // collection apart.
+    fn sum(&self) -> Option<u64>;
+}
+
+// All the lists that implement Stack look the same from here.
+macro_rules! stack_subject {
+    ($name:expr, $ty:ty, $sum:expr) => {
+        impl Subject for $ty {
+            const NAME: &'static str = $name;
+
+            fn new() -> Self {
+                <$ty>::new()
+            }
+
+            fn push(&mut self, x: u64) {
+                Stack::push(self, x)
+            }
+
+            fn pop(&mut self) -> Option<u64> {
+                Stack::pop(self)
+            }
+
+            fn peek(&self) -> Option<u64> {
+                Stack::peek(self).map(|x| *x)
+            }
+
+            fn sum(&self) -> Option<u64> {
+                let sum: fn(&Self) -> Option<u64> = $sum;
+                sum(self)
+            }
+        }
+    };
+}
+
+stack_subject!("second", second::List<u64>, |list| Some(list.iter().sum()));
+stack_subject!("third", third::List<u64>, |list| Some(list.iter().sum()));
+stack_subject!("fourth", fourth::List<u64>, |_| None);
+stack_subject!("sixth", sixth::LinkedList<u64>, |list| Some(
+    list.iter().sum()
+));
+
+// first::List only holds i32s, so it gets its own wrapper.
+impl Subject for first::List {
+    const NAME: &'static str = "first";
+
+    fn new() -> Self {
+        first::List::new()
+    }
+
+    fn push(&mut self, x: u64) {
+        Stack::push(self, x as i32)
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        Stack::pop(self).map(|x| x as u64)
+    }
+
+    fn peek(&self) -> Option<u64> {
+        Stack::peek(self).map(|x| *x as u64)
+    }
+
+    fn sum(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl Subject for fifth::raw_pointers::List<u64> {
+    const NAME: &'static str = "fifth";
+
+    fn new() -> Self {
+        fifth::raw_pointers::List::new()
+    }
+
+    fn push(&mut self, x: u64) {
+        Queue::push_back(self, x)
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        Queue::pop_front(self)
+    }
+
+    fn peek(&self) -> Option<u64> {
+        Queue::peek_front(self).copied()
+    }
+
+    fn sum(&self) -> Option<u64> {
+        Some(self.iter().sum())
+    }
+}
+
+impl Subject for lists::silly1::List<u64> {
+    const NAME: &'static str = "silly1";
+
+    fn new() -> Self {
+        lists::silly1::List::new()
+    }
+
+    fn push(&mut self, x: u64) {
+        self.push_left(x)
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        self.pop_left()
+    }
+
+    fn peek(&self) -> Option<u64> {
+        self.peek_left().copied()
+    }
+
+    fn sum(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl Subject for Vec<u64> {
+    const NAME: &'static str = "Vec";
+
+    fn new() -> Self {
+        Vec::new()
+    }
+
+    fn push(&mut self, x: u64) {
+        Vec::push(self, x)
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        Vec::pop(self)
+    }
+
+    fn peek(&self) -> Option<u64> {
+        self.last().copied()
+    }
+
+    fn sum(&self) -> Option<u64> {
+        Some(self.iter().sum())
+    }
+}
+
+impl Subject for VecDeque<u64> {
+    const NAME: &'static str = "VecDeque";
+
+    fn new() -> Self {
+        VecDeque::new()
+    }
+
+    fn push(&mut self, x: u64) {
+        self.push_back(x)
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        self.pop_front()
+    }
+
+    fn peek(&self) -> Option<u64> {
+        self.front().copied()
+    }
+
+    fn sum(&self) -> Option<u64> {
+        Some(self.iter().sum())
+    }
+}
+
+impl Subject for LinkedList<u64> {
+    const NAME: &'static str = "std LinkedList";
+
+    fn new() -> Self {
+        LinkedList::new()
+    }
+
+    fn push(&mut self, x: u64) {
+        self.push_back(x)
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        self.pop_back()
+    }
+
+    fn peek(&self) -> Option<u64> {
+        self.back().copied()
+    }
+
+    fn sum(&self) -> Option<u64> {
+        Some(self.iter().sum())
+    }
+}
+
+struct Row {
+    name: &'static str,
+    n: usize,
+    // Nanoseconds per element
+    push: f64,
+    pop: f64,
+    iter: Option<f64>,
+    peek: f64,
+    peak_bytes: f64,
+}
+
+// Enough repetitions that every measurement covers about this many elements,
+// so the small sizes aren't lost in timer noise.
+const ELEMENTS_PER_MEASUREMENT: usize = 1_000_000;
+
+fn filled<S: Subject>(n: usize) -> S {
+    let mut list = S::new();
+    for x in 0..n as u64 {
+        list.push(x);
+    }
+    list
+}
+
+fn per_elem(total: Duration, reps: usize, n: usize) -> f64 {
+    total.as_nanos() as f64 / (reps * n) as f64
+}
+
+fn measure<S: Subject>(n: usize) -> Row {
+    let reps = (ELEMENTS_PER_MEASUREMENT / n).max(1);
+
+    // Building and tearing down aren't part of what's measured, only the
+    // operation itself.
+    let mut push = Duration::ZERO;
+    for _ in 0..reps {
+        let mut list = S::new();
+        let start = Instant::now();
+        for x in 0..n as u64 {
+            list.push(black_box(x));
+        }
+        push += start.elapsed();
+        black_box(&list);
+    }
+
+    let mut pop = Duration::ZERO;
+    for _ in 0..reps {
+        let mut list = filled::<S>(n);
+        let start = Instant::now();
+        while let Some(x) = list.pop() {
+            black_box(x);
+        }
+        pop += start.elapsed();
+    }
+
+    let list = filled::<S>(n);
+    let start = Instant::now();
+    let mut iterable = true;
+    for _ in 0..reps {
+        iterable &= black_box(black_box(&list).sum()).is_some();
+    }
+    let iter = iterable.then(|| per_elem(start.elapsed(), reps, n));
+
+    let start = Instant::now();
+    for _ in 0..reps * n {
+        black_box(black_box(&list).peek());
+    }
+    let peek = per_elem(start.elapsed(), reps, n);
+    drop(list);
+
+    let before = CURRENT.load(Relaxed);
+    PEAK.store(before, Relaxed);
+    let list = filled::<S>(n);
+    let peak_bytes = (PEAK.load(Relaxed) - before) as f64 / n as f64;
+    drop(list);
+
+    Row {
+        name: S::NAME,
+        n,
+        push: per_elem(push, reps, n),
+        pop: per_elem(pop, reps, n),
+        iter,
+        peek,
+        peak_bytes,
+    }
+}
+
+fn main() {
+    let mut csv = false;
+    let mut max = 1_000_000;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--csv" => csv = true,
+            "--max" => {
+                max = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .expect("--max takes a number")
+            }
+            // cargo bench passes this to every bench target
+            "--bench" => {}
+            _ => {
+                eprintln!("usage: lists [--csv] [--max <elements>]");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let sizes: Vec<usize> = (1..=6)
+        .map(|exp| 10usize.pow(exp))
+        .filter(|&n| n <= max)
+        .collect();
+    let benches: [fn(usize) -> Row; 10] = [
+        measure::<first::List>,
+        measure::<second::List<u64>>,
+        measure::<third::List<u64>>,
+        measure::<fourth::List<u64>>,
+        measure::<fifth::raw_pointers::List<u64>>,
+        measure::<sixth::LinkedList<u64>>,
+        measure::<lists::silly1::List<u64>>,
+        measure::<Vec<u64>>,
+        measure::<VecDeque<u64>>,
+        measure::<LinkedList<u64>>,
+    ];
+
+    if csv {
+        println!("list,n,push_ns,pop_ns,iter_ns,peek_ns,peak_bytes_per_elem");
+    } else {
+        println!(
+            "{:<15} {:>8} {:>9} {:>9} {:>9} {:>9} {:>11}",
+            "list", "n", "push ns", "pop ns", "iter ns", "peek ns", "bytes/elem"
+        );
+    }
+    for &n in &sizes {
+        for bench in &benches {
+            let row = bench(n);
+            if csv {
+                println!(
+                    "{},{},{:.2},{:.2},{},{:.2},{:.1}",
+                    row.name,
+                    row.n,
+                    row.push,
+                    row.pop,
+                    row.iter.map_or(String::new(), |ns| format!("{:.2}", ns)),
+                    row.peek,
+                    row.peak_bytes
+                );
+            } else {
+                println!(
+                    "{:<15} {:>8} {:>9.2} {:>9.2} {:>9} {:>9.2} {:>11.1}",
+                    row.name,
+                    row.n,
+                    row.push,
+                    row.pop,
+                    row.iter.map_or("-".to_string(), |ns| format!("{:.2}", ns)),
+                    row.peek,
+                    row.peak_bytes
+                );
+            }
+        }
+        if !csv {
+            println!();
+        }
+    }
+}
@@ -0,0 +1,582 @@
+// A little shell for poking at the lists by hand:
+//
+//     $ cargo run --bin lists-repl -- fourth
+//     > push_back 1
+//     fourth: [1]
+//     > push_front 0
+//     fourth: [0, 1]
+//
+// Type `help` for the commands. Build with `--features viz` to get `dot`, and
+// with `--features debug-invariants` to have every command followed by an
+// invariant check on the pointer based lists.
+use code::traits::{Deque, Queue, Stack};
+use code::{fifth, first, fourth, second, sixth, third};
+use lists::silly1;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+const IMPLEMENTATIONS: &str = "first, second, third, fourth, fifth, sixth, silly1";
+
+const HELP: &str = "\
+shell commands:
+  use <list>          start over with an empty list of another kind
+  new                 start over with an empty list of the same kind
+  iter                print the contents, front to back
+  dump                print a diagram of the nodes
+  dot                 print the node graph for Graphviz (needs --features viz)
+  diagram on|off      print the diagram after every command
+  help                this text
+  quit                leave";
+
+type Output = Option<Result<String, String>>;
+
+// One list being exercised. Every kind understands its own set of commands
+// on top of the shell's.
+trait Session {
+    fn name(&self) -> &'static str;
+    fn help(&self) -> &'static str;
+    // Runs `cmd`, or returns None if this kind of list doesn't have it.
+    fn run(&mut self, cmd: &str, args: &[&str]) -> Output;
+    // Front to back. Takes &mut because the lists without an iterator have to
+    // be taken apart and put back together to look inside.
+    fn contents(&mut self) -> Vec<i32>;
+    fn diagram(&mut self) -> String;
+    // Only there when built with the viz feature.
+    fn dot(&self) -> Option<String> {
+        None
+    }
+    // Only does something with the debug-invariants feature.
+    fn check(&self) {}
+}
+
+fn open(name: &str) -> Option<Box<dyn Session>> {
+    Some(match name {
+        "first" => Box::new(first::List::new()),
+        "second" => Box::new(second::List::new()),
+        "third" => Box::new(third::List::new()),
+        "fourth" => Box::new(fourth::List::new()),
+        "fifth" => Box::new(fifth::raw_pointers::List::new()),
+        "sixth" => Box::new(SixthSession {
+            list: sixth::LinkedList::new(),
+            cursor: None,
+        }),
+        "silly1" => Box::new(silly1::List::new()),
+        _ => return None,
+    })
+}
+
+fn value(args: &[&str]) -> Result<i32, String> {
+    match args {
+        [arg] => arg.parse().map_err(|_| format!("`{}` is not an i32", arg)),
+        _ => Err("expected one number".to_string()),
+    }
+}
+
+fn shown<T: std::fmt::Debug>(elem: Option<T>) -> Result<String, String> {
+    Ok(match elem {
+        Some(elem) => format!("{:?}", elem),
+        None => "None".to_string(),
+    })
+}
+
+// Renders `head -> [1] -> [2] -> None` and friends. The element at `mark`
+// gets parentheses, that's where a cursor sits.
+fn chain(head: &str, items: &[i32], link: &str, end: &str, mark: Option<usize>) -> String {
+    let nodes: Vec<_> = items
+        .iter()
+        .enumerate()
+        .map(|(i, x)| match mark {
+            Some(m) if m == i => format!("([{}])", x),
+            _ => format!("[{}]", x),
+        })
+        .collect();
+    if nodes.is_empty() {
+        format!("{} -> None", head)
+    } else {
+        format!("{} -> {}{}", head, nodes.join(link), end)
+    }
+}
+
+fn stack_command<S: Stack<i32>>(list: &mut S, cmd: &str, args: &[&str]) -> Output {
+    Some(match cmd {
+        "push" => value(args).map(|x| {
+            list.push(x);
+            String::new()
+        }),
+        "pop" => shown(list.pop()),
+        "peek" => shown(list.peek().map(|x| *x)),
+        _ => return None,
+    })
+}
+
+fn queue_command<Q: Queue<i32>>(list: &mut Q, cmd: &str, args: &[&str]) -> Output {
+    Some(match cmd {
+        "push_back" => value(args).map(|x| {
+            list.push_back(x);
+            String::new()
+        }),
+        "pop_front" => shown(list.pop_front()),
+        "peek_front" => shown(list.peek_front().map(|x| *x)),
+        _ => return None,
+    })
+}
+
+fn deque_command<D: Deque<i32>>(list: &mut D, cmd: &str, args: &[&str]) -> Output {
+    queue_command(list, cmd, args).or_else(|| {
+        Some(match cmd {
+            "push_front" => value(args).map(|x| {
+                list.push_front(x);
+                String::new()
+            }),
+            "pop_back" => shown(list.pop_back()),
+            "peek_back" => shown(list.peek_back().map(|x| *x)),
+            _ => return None,
+        })
+    })
+}
+
+// Pops everything off a stack and pushes it back in the same order.
+fn drain_stack<S: Stack<i32>>(list: &mut S) -> Vec<i32> {
+    let items: Vec<_> = std::iter::from_fn(|| list.pop()).collect();
+    for &x in items.iter().rev() {
+        list.push(x);
+    }
+    items
+}
+
+impl Session for first::List {
+    fn name(&self) -> &'static str {
+        "first"
+    }
+
+    fn help(&self) -> &'static str {
+        "first: a stack of i32s\n  push <n>, pop, peek"
+    }
+
+    fn run(&mut self, cmd: &str, args: &[&str]) -> Output {
+        stack_command(self, cmd, args)
+    }
+
+    // No iterator at all, so pop everything and push it back
+    fn contents(&mut self) -> Vec<i32> {
+        drain_stack(self)
+    }
+
+    fn diagram(&mut self) -> String {
+        chain("head", &self.contents(), " -> ", " -> Empty", None)
+    }
+}
+
+impl Session for second::List<i32> {
+    fn name(&self) -> &'static str {
+        "second"
+    }
+
+    fn help(&self) -> &'static str {
+        "second: a generic stack\n  push <n>, pop, peek"
+    }
+
+    fn run(&mut self, cmd: &str, args: &[&str]) -> Output {
+        stack_command(self, cmd, args)
+    }
+
+    fn contents(&mut self) -> Vec<i32> {
+        self.iter().copied().collect()
+    }
+
+    fn diagram(&mut self) -> String {
+        chain("head", &self.contents(), " -> ", " -> None", None)
+    }
+
+    #[cfg(feature = "viz")]
+    fn dot(&self) -> Option<String> {
+        Some(self.to_dot())
+    }
+}
+
+impl Session for third::List<i32> {
+    fn name(&self) -> &'static str {
+        "third"
+    }
+
+    fn help(&self) -> &'static str {
+        "third: a persistent stack, each push makes a new version\n  push <n>, pop, peek"
+    }
+
+    fn run(&mut self, cmd: &str, args: &[&str]) -> Output {
+        stack_command(self, cmd, args)
+    }
+
+    fn contents(&mut self) -> Vec<i32> {
+        self.iter().copied().collect()
+    }
+
+    fn diagram(&mut self) -> String {
+        chain("head", &self.contents(), " -> ", " -> None", None)
+    }
+
+    #[cfg(feature = "viz")]
+    fn dot(&self) -> Option<String> {
+        Some(self.to_dot())
+    }
+}
+
+impl Session for fourth::List<i32> {
+    fn name(&self) -> &'static str {
+        "fourth"
+    }
+
+    fn help(&self) -> &'static str {
+        "fourth: a deque of Rc<RefCell> nodes\n  \
+         push_front <n>, push_back <n>, pop_front, pop_back, peek_front, peek_back"
+    }
+
+    fn run(&mut self, cmd: &str, args: &[&str]) -> Output {
+        deque_command(self, cmd, args)
+    }
+
+    // There is no borrowing iterator, the nodes are behind RefCells
+    fn contents(&mut self) -> Vec<i32> {
+        let items: Vec<_> = std::iter::from_fn(|| self.pop_front()).collect();
+        for &x in &items {
+            self.push_back(x);
+        }
+        items
+    }
+
+    fn diagram(&mut self) -> String {
+        chain("head", &self.contents(), " <-> ", " <- tail", None)
+    }
+
+    #[cfg(feature = "viz")]
+    fn dot(&self) -> Option<String> {
+        Some(self.to_dot())
+    }
+
+    #[cfg(feature = "debug-invariants")]
+    fn check(&self) {
+        self.check_invariants();
+    }
+}
+
+impl Session for fifth::raw_pointers::List<i32> {
+    fn name(&self) -> &'static str {
+        "fifth"
+    }
+
+    fn help(&self) -> &'static str {
+        "fifth: a queue on raw pointers\n  push_back <n>, pop_front, peek_front"
+    }
+
+    fn run(&mut self, cmd: &str, args: &[&str]) -> Output {
+        queue_command(self, cmd, args)
+    }
+
+    fn contents(&mut self) -> Vec<i32> {
+        self.iter().copied().collect()
+    }
+
+    fn diagram(&mut self) -> String {
+        chain("head", &self.contents(), " -> ", " <- tail", None)
+    }
+
+    #[cfg(feature = "viz")]
+    fn dot(&self) -> Option<String> {
+        Some(self.to_dot())
+    }
+
+    #[cfg(feature = "debug-invariants")]
+    fn check(&self) {
+        self.check_invariants();
+    }
+}
+
+// A CursorMut borrows the list, so between commands we only remember where
+// it was and walk a fresh one back there. None is the ghost.
+struct SixthSession {
+    list: sixth::LinkedList<i32>,
+    cursor: Option<usize>,
+}
+
+impl SixthSession {
+    fn cursor_command(&mut self, args: &[&str]) -> Result<String, String> {
+        let mut cursor = self.list.cursor_mut();
+        if let Some(index) = self.cursor {
+            for _ in 0..=index {
+                cursor.move_next();
+            }
+        }
+        let out = match args {
+            ["next"] => {
+                cursor.move_next();
+                Ok(String::new())
+            }
+            ["prev"] => {
+                cursor.move_prev();
+                Ok(String::new())
+            }
+            ["current"] => shown(cursor.current().copied()),
+            ["peek_next"] => shown(cursor.peek_next().copied()),
+            ["peek_prev"] => shown(cursor.peek_prev().copied()),
+            ["insert_before", arg] => value(&[*arg]).map(|x| {
+                cursor.insert_before(x);
+                String::new()
+            }),
+            ["insert_after", arg] => value(&[*arg]).map(|x| {
+                cursor.insert_after(x);
+                String::new()
+            }),
+            ["remove"] => shown(cursor.remove_current()),
+            ["split_before"] => Ok(format!("{:?}", cursor.split_before())),
+            ["split_after"] => Ok(format!("{:?}", cursor.split_after())),
+            _ => Err(
+                "cursor next|prev|current|peek_next|peek_prev|insert_before <n>|\
+                      insert_after <n>|remove|split_before|split_after"
+                    .to_string(),
+            ),
+        };
+        self.cursor = cursor.index();
+        out
+    }
+}
+
+impl Session for SixthSession {
+    fn name(&self) -> &'static str {
+        "sixth"
+    }
+
+    fn help(&self) -> &'static str {
+        "sixth: the production quality deque, with a cursor\n  \
+         push_front <n>, push_back <n>, pop_front, pop_back, peek_front, peek_back,\n  \
+         insert <i> <n>, remove <i>, sort, reverse_sort,\n  \
+         cursor next|prev|current|peek_next|peek_prev|insert_before <n>|insert_after <n>|\n         \
+         remove|split_before|split_after"
+    }
+
+    fn run(&mut self, cmd: &str, args: &[&str]) -> Output {
+        let out = match cmd {
+            "cursor" => Some(self.cursor_command(args)),
+            "insert" => Some(match args {
+                [at, x] => match (at.parse::<usize>(), value(&[*x])) {
+                    (Ok(at), Ok(x)) if at <= self.list.len() => {
+                        self.list.insert(at, x);
+                        Ok(String::new())
+                    }
+                    (Ok(_), Ok(_)) => Err("index out of bounds".to_string()),
+                    (_, Err(e)) => Err(e),
+                    (Err(_), _) => Err(format!("`{}` is not an index", at)),
+                },
+                _ => Err("insert <index> <n>".to_string()),
+            }),
+            "remove" => Some(match args {
+                [at] => at
+                    .parse()
+                    .map_err(|_| format!("`{}` is not an index", at))
+                    .and_then(|at| shown(self.list.remove(at))),
+                _ => Err("remove <index>".to_string()),
+            }),
+            "sort" => {
+                self.list.sort();
+                Some(Ok(String::new()))
+            }
+            "reverse_sort" => {
+                self.list.sort_by(|a, b| b.cmp(a));
+                Some(Ok(String::new()))
+            }
+            _ => deque_command(&mut self.list, cmd, args),
+        };
+        // Anything but the cursor's own commands may have moved the nodes
+        // out from under it, so it goes back to the ghost.
+        if cmd != "cursor" && out.is_some() {
+            self.cursor = None;
+        }
+        out
+    }
+
+    fn contents(&mut self) -> Vec<i32> {
+        self.list.iter().copied().collect()
+    }
+
+    fn diagram(&mut self) -> String {
+        let ghost = if self.cursor.is_none() {
+            " (ghost)"
+        } else {
+            ""
+        };
+        let items = self.contents();
+        chain("front", &items, " <-> ", " <- back", self.cursor) + ghost
+    }
+
+    #[cfg(feature = "viz")]
+    fn dot(&self) -> Option<String> {
+        Some(self.list.to_dot())
+    }
+
+    #[cfg(feature = "debug-invariants")]
+    fn check(&self) {
+        self.list.check_invariants();
+    }
+}
+
+impl Session for silly1::List<i32> {
+    fn name(&self) -> &'static str {
+        "silly1"
+    }
+
+    fn help(&self) -> &'static str {
+        "silly1: a zipper, two stacks back to back around a cursor\n  \
+         push_left <n>, push_right <n>, pop_left, pop_right, peek_left, peek_right,\n  \
+         left, right (move the cursor)"
+    }
+
+    fn run(&mut self, cmd: &str, args: &[&str]) -> Output {
+        Some(match cmd {
+            "push_left" => value(args).map(|x| {
+                self.push_left(x);
+                String::new()
+            }),
+            "push_right" => value(args).map(|x| {
+                self.push_right(x);
+                String::new()
+            }),
+            "pop_left" => shown(self.pop_left()),
+            "pop_right" => shown(self.pop_right()),
+            "peek_left" => shown(self.peek_left()),
+            "peek_right" => shown(self.peek_right()),
+            "left" => Ok(self.go_left().to_string()),
+            "right" => Ok(self.go_right().to_string()),
+            _ => return None,
+        })
+    }
+
+    fn contents(&mut self) -> Vec<i32> {
+        let (left, right) = silly1_sides(self);
+        left.into_iter().rev().chain(right).collect()
+    }
+
+    fn diagram(&mut self) -> String {
+        let (left, right) = silly1_sides(self);
+        let show = |side: &[i32]| -> String {
+            let nodes: Vec<_> = side.iter().map(|x| format!("[{}]", x)).collect();
+            if nodes.is_empty() {
+                "None".to_string()
+            } else {
+                nodes.join(" -> ")
+            }
+        };
+        format!("left: {} | right: {}", show(&left), show(&right))
+    }
+
+    #[cfg(feature = "viz")]
+    fn dot(&self) -> Option<String> {
+        Some(self.to_dot())
+    }
+}
+
+// Both stacks of the zipper, each starting next to the cursor. There's no
+// iterator, so they're popped off and pushed back.
+fn silly1_sides(list: &mut silly1::List<i32>) -> (Vec<i32>, Vec<i32>) {
+    let left: Vec<_> = std::iter::from_fn(|| list.pop_left()).collect();
+    let right: Vec<_> = std::iter::from_fn(|| list.pop_right()).collect();
+    for &x in left.iter().rev() {
+        list.push_left(x);
+    }
+    for &x in right.iter().rev() {
+        list.push_right(x);
+    }
+    (left, right)
+}
+
+fn main() {
+    let name = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "sixth".to_string());
+    let Some(mut session) = open(&name) else {
+        eprintln!("unknown list `{}`, pick one of: {}", name, IMPLEMENTATIONS);
+        std::process::exit(2);
+    };
+
+    // Only chat when there's someone to chat with, so piped scripts give
+    // output that's easy to diff.
+    let interactive = io::stdin().is_terminal();
+    if interactive {
+        println!("{}\n{}\n", session.help(), HELP);
+    }
+    let mut show_diagram = false;
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        if interactive {
+            print!("{}> ", session.name());
+            io::stdout().flush().unwrap();
+        }
+        let Some(Ok(line)) = lines.next() else { break };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&cmd, args)) = words.split_first() else {
+            continue;
+        };
+
+        let out = match cmd {
+            "quit" | "exit" => break,
+            "help" => {
+                println!("{}\n{}", session.help(), HELP);
+                continue;
+            }
+            "use" => match args.first().and_then(|name| open(name)) {
+                Some(new) => {
+                    session = new;
+                    Ok(String::new())
+                }
+                None => Err(format!("use one of: {}", IMPLEMENTATIONS)),
+            },
+            "new" => {
+                session = open(session.name()).unwrap();
+                Ok(String::new())
+            }
+            // Just looking, no need to show the list again afterwards
+            "iter" => {
+                println!("{:?}", session.contents());
+                continue;
+            }
+            "dump" => {
+                println!("{}", session.diagram());
+                continue;
+            }
+            "dot" => {
+                match session.dot() {
+                    Some(dot) => print!("{}", dot),
+                    None => println!("error: built without --features viz"),
+                }
+                continue;
+            }
+            "diagram" => match args {
+                ["on"] => {
+                    show_diagram = true;
+                    Ok(String::new())
+                }
+                ["off"] => {
+                    show_diagram = false;
+                    Ok(String::new())
+                }
+                _ => Err("diagram on|off".to_string()),
+            },
+            _ => session
+                .run(cmd, args)
+                .unwrap_or_else(|| Err(format!("{} has no `{}`, try `help`", session.name(), cmd))),
+        };
+
+        match out {
+            Ok(out) => {
+                if !out.is_empty() {
+                    println!("=> {}", out);
+                }
+                session.check();
+                println!("{}: {:?}", session.name(), session.contents());
+                if show_diagram {
+                    println!("{}", session.diagram());
+                }
+            }
+            Err(err) => println!("error: {}", err),
+        }
+    }
+}
@@ -22,9 +22,11 @@ pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
 }
 
+use crate::traits::Queue;
 use std::ptr;
 
 impl<T> List<T> {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         List {
             head: ptr::null_mut(),
@@ -36,7 +38,7 @@ impl<T> List<T> {
         unsafe {
             // Immediately convert the Box into a raw pointer
             let new_tail = Box::into_raw(Box::new(Node {
-                elem: elem,
+                elem,
                 next: ptr::null_mut(),
             }));
 
@@ -69,6 +71,7 @@ impl<T> List<T> {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn into_iter(self) -> IntoIter<T> {
         IntoIter(self)
     }
@@ -98,9 +101,97 @@ impl<T> List<T> {
     }
 }
 
+#[cfg(any(test, feature = "debug-invariants"))]
+impl<T> List<T> {
+    // tail has to be the last node reachable from head, or null with head.
+    pub fn check_invariants(&self) {
+        unsafe {
+            if self.head.is_null() {
+                assert!(self.tail.is_null(), "list is empty but tail is {:p}", self.tail);
+                return;
+            }
+            // slow moves at half speed, if fast ever catches it there's a cycle.
+            let mut slow = self.head;
+            let mut last = self.head;
+            let mut index = 0;
+            while !(*last).next.is_null() {
+                last = (*last).next;
+                index += 1;
+                if index % 2 == 0 {
+                    slow = (*slow).next;
+                }
+                assert!(last != slow, "cycle in next links at node {} ({:p})", index, last);
+            }
+            assert!(
+                last == self.tail,
+                "tail is {:p} but the last node reachable from head is node {} ({:p})",
+                self.tail,
+                index,
+                last
+            );
+        }
+    }
+}
+
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
-        while let Some(_) = self.pop() {}
+        // Raw pointers don't free anything on their own, so if an element's
+        // drop panics the guard carries on popping the rest while we unwind.
+        struct DropGuard<'a, T>(&'a mut List<T>);
+
+        impl<T> Drop for DropGuard<'_, T> {
+            fn drop(&mut self) {
+                while self.0.pop().is_some() {}
+            }
+        }
+
+        let guard = DropGuard(self);
+        while guard.0.pop().is_some() {}
+    }
+}
+
+// Graphviz picture of the nodes, see lists::viz.
+#[cfg(feature = "viz")]
+impl<T: std::fmt::Debug> List<T> {
+    pub fn to_dot(&self) -> String {
+        use lists::viz::{self, Dot};
+
+        let mut dot = Dot::new("fifth::raw_pointers::List");
+        dot.handle("list", "List");
+        if !self.tail.is_null() {
+            dot.back_edge("list", &viz::id(self.tail), "tail");
+        }
+        let (mut from, mut label) = ("list".to_string(), "head");
+        let mut link = self.head;
+        while !link.is_null() {
+            let id = viz::id(link);
+            unsafe {
+                dot.node(&id, &[format!("elem: {}", viz::elem(&(*link).elem))]);
+                link = (*link).next;
+            }
+            dot.edge(&from, &id, label);
+            (from, label) = (id, "next");
+        }
+        dot.finish()
+    }
+}
+
+impl<T> Queue<T> for List<T> {
+    type Peek<'a>
+        = &'a T
+    where
+        T: 'a;
+
+    fn push_back(&mut self, elem: T) {
+        List::push(self, elem)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        List::pop(self)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        List::peek(self)
     }
 }
 
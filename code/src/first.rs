@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 use std::mem;
+use crate::traits::Stack;
 
 #[derive(Debug)]
 pub struct List {
@@ -19,6 +20,7 @@ struct Node {
 }
 
 impl List {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         List { head: Link::Empty }
     }
@@ -32,7 +34,7 @@ impl List {
     // to new value and return previous value as owned.
     pub fn push(&mut self, elem: i32) {
         let node = Node {
-            elem: elem,
+            elem,
             // replace moves Link in head returns previos value.
             next: mem::replace(&mut self.head, Link::Empty),
         };
@@ -70,3 +72,24 @@ impl Drop for List {
         }
     }
 }
+
+// The book never gives this list a peek, so the trait has to look at the
+// head itself.
+impl Stack<i32> for List {
+    type Peek<'a> = &'a i32;
+
+    fn push(&mut self, elem: i32) {
+        List::push(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<i32> {
+        List::pop(self)
+    }
+
+    fn peek(&self) -> Option<&i32> {
+        match &self.head {
+            Link::Empty => None,
+            Link::More(node) => Some(&node.elem),
+        }
+    }
+}
@@ -3,6 +3,8 @@
 use std::cell::{Ref, RefCell, RefMut};
 use std::rc::Rc;
 
+use crate::traits::{Deque, Queue, Stack};
+
 pub struct List<T> {
     head: Link<T>,
     tail: Link<T>,
@@ -19,7 +21,7 @@ struct Node<T> {
 impl<T> Node<T> {
     fn new(elem: T) -> Rc<RefCell<Self>> {
         Rc::new(RefCell::new(Node {
-            elem: elem,
+            elem,
             prev: None,
             next: None,
         }))
@@ -27,6 +29,7 @@ impl<T> Node<T> {
 }
 
 impl<T> List<T> {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         List {
             head: None,
@@ -75,7 +78,7 @@ impl<T> List<T> {
     // http://localhost:3000/fourth-peek.html
     // it's really a lot in it and explanation is lengthy, bottom line RefCell is not easy
     // and must learn api.
-    pub fn peek_front(&self) -> Option<Ref<T>> {
+    pub fn peek_front(&self) -> Option<Ref<'_, T>> {
         self.head
             .as_ref()
             // Converts from &Option<T> to Option<&T> in this context as if
@@ -116,29 +119,96 @@ impl<T> List<T> {
         })
     }
 
-    pub fn peek_back(&self) -> Option<Ref<T>> {
+    pub fn peek_back(&self) -> Option<Ref<'_, T>> {
         self.tail
             .as_ref()
             .map(|node| Ref::map(node.borrow(), |node| &node.elem))
     }
 
     // Methods for mutable operations
-    pub fn peek_back_mut(&mut self) -> Option<RefMut<T>> {
+    pub fn peek_back_mut(&mut self) -> Option<RefMut<'_, T>> {
         self.tail
             .as_ref()
             .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
     }
 
-    pub fn peek_front_mut(&mut self) -> Option<RefMut<T>> {
+    pub fn peek_front_mut(&mut self) -> Option<RefMut<'_, T>> {
         self.head
             .as_ref()
             .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
     }
 }
 
+#[cfg(any(test, feature = "debug-invariants"))]
+impl<T> List<T> {
+    // Every node must be owned exactly twice: by whoever is in front of it
+    // (prev.next or head) and whoever is behind it (next.prev or tail).
+    // Anything else is either a leak waiting to happen or a dangling link.
+    pub fn check_invariants(&self) {
+        let mut prev: Link<T> = None;
+        let mut cur = self.head.clone();
+        let mut index = 0;
+        while let Some(node) = cur {
+            // +1 for the clone we're holding in `cur`
+            assert!(
+                Rc::strong_count(&node) == 3,
+                "node {} ({:p}) has {} strong refs, expected 2",
+                index,
+                Rc::as_ptr(&node),
+                Rc::strong_count(&node) - 1
+            );
+            assert!(
+                Rc::weak_count(&node) == 0,
+                "node {} ({:p}) has {} weak refs",
+                index,
+                Rc::as_ptr(&node),
+                Rc::weak_count(&node)
+            );
+            let borrowed = node.borrow();
+            let linked_back = match (&borrowed.prev, &prev) {
+                (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            };
+            assert!(
+                linked_back,
+                "node {} ({:p}) prev link doesn't point at the node before it",
+                index,
+                Rc::as_ptr(&node)
+            );
+            cur = borrowed.next.clone();
+            drop(borrowed);
+            prev = Some(node);
+            index += 1;
+        }
+        let tail_ok = match (&self.tail, &prev) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        assert!(
+            tail_ok,
+            "tail doesn't point at the last node reachable from head (node {})",
+            index
+        );
+    }
+}
+
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
-        while self.pop_front().is_some() {}
+        // If an element's drop panics the rest of the nodes would be left
+        // holding each other through their Rc links and leak. The guard keeps
+        // popping while we unwind; a second panic aborts, like std's lists.
+        struct DropGuard<'a, T>(&'a mut List<T>);
+
+        impl<T> Drop for DropGuard<'_, T> {
+            fn drop(&mut self) {
+                while self.0.pop_front().is_some() {}
+            }
+        }
+
+        let guard = DropGuard(self);
+        while guard.0.pop_front().is_some() {}
     }
 }
 
@@ -146,6 +216,7 @@ impl<T> Drop for List<T> {
 pub struct IntoIter<T>(List<T>);
 
 impl<T> List<T> {
+    #[allow(clippy::should_implement_trait)]
     pub fn into_iter(self) -> IntoIter<T> {
         IntoIter(self)
     }
@@ -165,3 +236,92 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
 }
 // Iter and IterMut are not possible on current set up with Cell and Rc simply
 // because of how they are implimented in Rust. Read chapter on fourth-iteration or Bad Deque.
+
+// Graphviz picture of the nodes, see lists::viz. Every node but the ends is
+// held by its two neighbours, the ends by one neighbour and the list.
+#[cfg(feature = "viz")]
+impl<T: std::fmt::Debug> List<T> {
+    pub fn to_dot(&self) -> String {
+        use lists::viz::{self, Dot};
+
+        let mut dot = Dot::new("fourth::List");
+        dot.handle("list", "List");
+        if let Some(tail) = &self.tail {
+            dot.back_edge("list", &viz::id(Rc::as_ptr(tail)), "tail");
+        }
+        let (mut from, mut label) = ("list".to_string(), "head");
+        let mut link = self.head.clone();
+        while let Some(node) = link {
+            let id = viz::id(Rc::as_ptr(&node));
+            let borrowed = node.borrow();
+            dot.node(
+                &id,
+                &[
+                    format!("elem: {}", viz::elem(&borrowed.elem)),
+                    // Minus the clone we're holding
+                    format!("rc: {}", Rc::strong_count(&node) - 1),
+                ],
+            );
+            dot.edge(&from, &id, label);
+            if let Some(prev) = &borrowed.prev {
+                dot.back_edge(&id, &viz::id(Rc::as_ptr(prev)), "prev");
+            }
+            (from, label) = (id, "next");
+            link = borrowed.next.clone();
+        }
+        dot.finish()
+    }
+}
+
+// The front doubles as the top of the stack.
+impl<T> Stack<T> for List<T> {
+    type Peek<'a>
+        = Ref<'a, T>
+    where
+        T: 'a;
+
+    fn push(&mut self, elem: T) {
+        List::push_front(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        List::pop_front(self)
+    }
+
+    fn peek(&self) -> Option<Ref<'_, T>> {
+        List::peek_front(self)
+    }
+}
+
+impl<T> Queue<T> for List<T> {
+    type Peek<'a>
+        = Ref<'a, T>
+    where
+        T: 'a;
+
+    fn push_back(&mut self, elem: T) {
+        List::push_back(self, elem)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        List::pop_front(self)
+    }
+
+    fn peek_front(&self) -> Option<Ref<'_, T>> {
+        List::peek_front(self)
+    }
+}
+
+impl<T> Deque<T> for List<T> {
+    fn push_front(&mut self, elem: T) {
+        List::push_front(self, elem)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        List::pop_back(self)
+    }
+
+    fn peek_back(&self) -> Option<Ref<'_, T>> {
+        List::peek_back(self)
+    }
+}
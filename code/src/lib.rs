@@ -3,4 +3,5 @@ pub mod second;
 pub mod third;
 pub mod fourth;
 pub mod fifth;
-pub mod sixth;
\ No newline at end of file
+pub mod sixth;
+pub mod traits;
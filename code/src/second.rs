@@ -1,13 +1,20 @@
 #![allow(dead_code)]
-#[derive(Debug)]
+use crate::traits::Stack;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+
 pub struct List<T> {
     // List is simple wrapper around type Option.
     head: Link<T>,
+    // Number of nodes, kept up to date by everything that adds or removes
+    // them so len() doesn't have to walk the list.
+    len: usize,
 }
 
 type Link<T> = Option<Box<Node<T>>>;
 
-#[derive(Debug)]
 struct Node<T> {
     elem: T,
     next: Link<T>,
@@ -15,25 +22,35 @@ struct Node<T> {
 
 impl<T> List<T> {
     pub fn new() -> Self {
-        List { head: None }
+        List { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
     // Since we using Option now instead of operating memory on low level we use Option.take()
     // https://doc.rust-lang.org/stable/std/option/enum.Option.html#method.take
     pub fn push(&mut self, elem: T) {
         let node = Node {
-            elem: elem,
+            elem,
             next: self.head.take(),
         };
 
         let link = Some(Box::new(node));
         self.head = link;
+        self.len += 1;
     }
 
     // map on option does something with Option and returns Option
     pub fn pop(&mut self) -> Option<T> {
         self.head.take().map(|node| {
             self.head = node.next;
+            self.len -= 1;
             node.elem
         })
     }
@@ -45,6 +62,50 @@ impl<T> List<T> {
     pub fn peek_mut(&mut self) -> Option<&mut T> {
         self.head.as_mut().map(|node| &mut node.elem)
     }
+
+    // Takes the nodes off one at a time and pushes them onto a new chain, the
+    // same way pop and push do it but without unboxing the elements. Nodes stay
+    // where they are in memory, only next pointers change.
+    pub fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut cur_link = self.head.take();
+        while let Some(mut boxed_node) = cur_link {
+            cur_link = boxed_node.next.take();
+            boxed_node.next = reversed;
+            reversed = Some(boxed_node);
+        }
+        self.head = reversed;
+    }
+
+    // Moves all of other's nodes below ours, so self pops its own elements
+    // first. We walk down to our last link and hang other's head there, other
+    // is left empty.
+    pub fn append(&mut self, other: &mut Self) {
+        let mut link = &mut self.head;
+        while let Some(node) = link {
+            link = &mut node.next;
+        }
+        *link = other.head.take();
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    // Splits the list in two. self keeps the `at` elements on top, everything
+    // below them is returned as a new List.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+        let mut link = &mut self.head;
+        for _ in 0..at {
+            // There are at least `at` nodes, so this never runs off the end
+            link = &mut link.as_mut().unwrap().next;
+        }
+        let output = List {
+            head: link.take(),
+            len: self.len - at,
+        };
+        self.len = at;
+        output
+    }
 }
 
 // Implementing cleanup must free memory used by List
@@ -64,12 +125,82 @@ impl<T> Drop for List<T> {
     }
 }
 
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Pushing the clones would turn the copy upside down, so they go in through a
+// cursor parked on the last node instead, each one straight in after it.
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        let mut list = Self::new();
+        let mut cursor = list.cursor_mut();
+        for elem in self {
+            cursor.insert_after(elem.clone());
+            cursor.move_next();
+        }
+        list
+    }
+}
+
+// Extending a stack pushes each element in turn, so the last one ends up on
+// top.
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+// Same as new() and then extend(): pushed in order, the last element ends up
+// on top.
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+// Prints the elements top first like a Vec would, instead of the nested
+// Some(Node { .. }) a derive gives. That one also recursed through every node.
+impl<T: Debug> Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        // Comparing len first is O(1) and saves a walk for most mismatches
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+impl<T: Hash> Hash for List<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Same as sixth: the len keeps lists of lists from colliding
+        self.len().hash(state);
+        for elem in self {
+            elem.hash(state);
+        }
+    }
+}
+
 // Tuple structs are an alternative form of struct,
 // useful for trivial wrappers around other types.
 pub struct IntoIter<T>(List<T>);
 
-impl<T> List<T> {
-    pub fn into_iter(self) -> IntoIter<T> {
+impl<T> IntoIterator for List<T> {
+    type IntoIter = IntoIter<T>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
         IntoIter(self)
     }
 }
@@ -81,11 +212,22 @@ impl<T> Iterator for IntoIter<T> {
         // every other call to next simply pops head which is Option of boxed value.
         self.0.pop()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
 }
 
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+// Once the list is empty pop keeps returning None.
+impl<T> FusedIterator for IntoIter<T> {}
+
 // implimentation of  Iter for our List.
 pub struct Iter<'a, T> {
     next: Option<&'a Node<T>>,
+    // Nodes still to come, for size_hint
+    len: usize,
 }
 
 impl<T> List<T> {
@@ -95,10 +237,20 @@ impl<T> List<T> {
             next: self.head.as_deref(),
             // our Iter storing borrowed Node, but head in the List is Option<Box<Node<T>>>
             // need to dereference
+            len: self.len,
         }
     }
 }
 
+impl<'a, T> IntoIterator for &'a List<T> {
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
@@ -106,29 +258,357 @@ impl<'a, T> Iterator for Iter<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.next.map(|node| {
             self.next = node.next.as_deref();
+            self.len -= 1;
             &node.elem
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
 }
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+// After the last node next stays None.
+impl<T> FusedIterator for Iter<'_, T> {}
+
 pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
+    len: usize,
 }
 
 impl<T> List<T> {
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             next: self.head.as_deref_mut(),
+            len: self.len,
         }
     }
 }
 
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type IntoIter = IterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 impl<'a, T> Iterator for IterMut<'a, T> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.next.take().map(|node| {
             self.next = node.next.as_deref_mut();
+            self.len -= 1;
             &mut node.elem
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+// A cursor for editing the list in the middle. Nodes only know their next, so
+// unlike sixth's cursor this one can only go forward, and all the editing
+// happens right after it. It starts on a "ghost" before the head. Stepping
+// past the last node puts it on a ghost at the end, where it stays and where
+// insert_after adds to the bottom of the list.
+pub struct CursorMut<'a, T> {
+    // Element of the node we're on, None on either ghost.
+    cur: Option<&'a mut T>,
+    // The link after the cursor: the list's head while we're on the first
+    // ghost, the current node's next after that. It is only None for the
+    // moment move_next spends swapping it for the next one.
+    next: Option<&'a mut Link<T>>,
+    // How many nodes come before `next`, and the list's len. Between them
+    // they tell how much split_after takes.
+    index: usize,
+    len: &'a mut usize,
+}
+
+impl<T> List<T> {
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            cur: None,
+            next: Some(&mut self.head),
+            index: 0,
+            len: &mut self.len,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    fn next_link(&mut self) -> &mut Link<T> {
+        self.next.as_mut().unwrap()
+    }
+
+    pub fn move_next(&mut self) {
+        let next = self.next.take().unwrap();
+        match next {
+            Some(node) => {
+                // Borrow the element and the next link of the node separately,
+                // so we can hand out one and edit through the other.
+                let Node { elem, next } = &mut **node;
+                self.cur = Some(elem);
+                self.next = Some(next);
+                self.index += 1;
+            }
+            None => {
+                // Nothing after us, we're on (or stay on) the ghost at the end
+                self.cur = None;
+                self.next = Some(next);
+            }
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.cur.as_deref_mut()
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        self.next_link().as_mut().map(|node| &mut node.elem)
+    }
+
+    // The new node goes between us and our next, the cursor doesn't move.
+    pub fn insert_after(&mut self, elem: T) {
+        let link = self.next_link();
+        let node = Node {
+            elem,
+            next: link.take(),
+        };
+        *link = Some(Box::new(node));
+        *self.len += 1;
+    }
+
+    // Same thing pop does, only on our next link instead of the head.
+    pub fn remove_next(&mut self) -> Option<T> {
+        let link = self.next.as_mut().unwrap();
+        link.take().map(|node| {
+            **link = node.next;
+            *self.len -= 1;
+            node.elem
+        })
+    }
+
+    // Everything after the cursor becomes its own list.
+    pub fn split_after(&mut self) -> List<T> {
+        let output = List {
+            head: self.next_link().take(),
+            len: *self.len - self.index,
+        };
+        *self.len = self.index;
+        output
+    }
+
+    // Puts all of input between us and our next: the rest of our list goes
+    // under input, and the result hangs off the cursor.
+    pub fn splice_after(&mut self, mut input: List<T>) {
+        let link = self.next.as_mut().unwrap();
+        let mut rest = List {
+            head: link.take(),
+            len: *self.len - self.index,
+        };
+        input.append(&mut rest);
+        **link = input.head.take();
+        *self.len = self.index + input.len;
+    }
+}
+
+// Bottom-up merge sort. Each pass takes the nodes off the list, merges pairs of
+// runs of `width` nodes into a new chain and puts that back, then the width
+// doubles. Only the Boxes get relinked, there is no recursion and no buffer.
+impl<T> List<T> {
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(Ord::cmp);
+    }
+
+    // A stable sort is a perfectly good unstable one, and with nodes there is
+    // nothing to be gained by giving up stability.
+    pub fn sort_unstable_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    // If `compare` panics the list is left partially sorted with every element
+    // still in it, see Runs.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        let mut width = 1;
+        while width < self.len {
+            let rest = self.head.take();
+            let mut runs = Runs::new(self);
+            runs.rest = rest;
+            let mut tail = &mut runs.out;
+            while runs.rest.is_some() {
+                runs.left = split_run(&mut runs.rest, width);
+                runs.right = split_run(&mut runs.rest, width);
+                tail = merge(tail, &mut runs.left, &mut runs.right, &mut compare);
+            }
+            // runs hands the merged nodes back to the list here
+            width *= 2;
+        }
+    }
+
+    // Merges two sorted lists into one sorted list. Equal elements from `a`
+    // come before the ones from `b`.
+    pub fn merge_sorted(mut a: Self, mut b: Self) -> Self
+    where
+        T: Ord,
+    {
+        let mut merged = List::new();
+        merged.len = a.len + b.len;
+        let mut runs = Runs::new(&mut merged);
+        runs.left = a.head.take();
+        runs.right = b.head.take();
+        merge(
+            &mut runs.out,
+            &mut runs.left,
+            &mut runs.right,
+            &mut Ord::cmp,
+        );
+        drop(runs);
+        merged
+    }
+}
+
+// The chains a merge works with, outside of any List while it runs. Dropping
+// this strings them back together onto the list. Normally everything is in
+// `out` by then, but if compare panics the unwind drops it too, and the list
+// gets back all its nodes instead of them being dropped one Box inside the
+// next.
+struct Runs<'a, T> {
+    list: &'a mut List<T>,
+    // Merged so far
+    out: Link<T>,
+    // The two runs being merged right now
+    left: Link<T>,
+    right: Link<T>,
+    // Not merged yet
+    rest: Link<T>,
+}
+
+impl<'a, T> Runs<'a, T> {
+    fn new(list: &'a mut List<T>) -> Self {
+        Runs {
+            list,
+            out: None,
+            left: None,
+            right: None,
+            rest: None,
+        }
+    }
+}
+
+impl<T> Drop for Runs<'_, T> {
+    fn drop(&mut self) {
+        let mut tail = &mut self.out;
+        for run in [&mut self.left, &mut self.right, &mut self.rest] {
+            if run.is_some() {
+                while let Some(node) = tail {
+                    tail = &mut node.next;
+                }
+                *tail = run.take();
+            }
+        }
+        self.list.head = self.out.take();
+    }
+}
+
+// Takes the first `n` nodes off `link`, or all of them if there are fewer.
+fn split_run<T>(link: &mut Link<T>, n: usize) -> Link<T> {
+    let mut run = link.take();
+    let mut end = &mut run;
+    for _ in 0..n {
+        match end {
+            Some(node) => end = &mut node.next,
+            None => break,
+        }
+    }
+    *link = end.take();
+    run
+}
+
+// Moves the nodes of two sorted runs onto `tail` in order and returns the new
+// tail. Every node is fully moved before compare is called again, so if it
+// panics each node is in exactly one of the chains.
+fn merge<'a, T, F: FnMut(&T, &T) -> Ordering>(
+    mut tail: &'a mut Link<T>,
+    left: &mut Link<T>,
+    right: &mut Link<T>,
+    compare: &mut F,
+) -> &'a mut Link<T> {
+    loop {
+        let from = match (left.as_deref(), right.as_deref()) {
+            // Right only goes first when it's strictly smaller, so equal
+            // elements keep their order and the sort is stable.
+            (Some(l), Some(r)) => {
+                if compare(&r.elem, &l.elem) == Ordering::Less {
+                    &mut *right
+                } else {
+                    &mut *left
+                }
+            }
+            // One run is used up and the rest of the other is already in
+            // order, so it goes on in one piece.
+            _ => {
+                *tail = left.take().or_else(|| right.take());
+                while let Some(node) = tail {
+                    tail = &mut node.next;
+                }
+                return tail;
+            }
+        };
+        let mut node = from.take().unwrap();
+        *from = node.next.take();
+        tail = &mut tail.insert(node).next;
+    }
+}
+
+// Graphviz picture of the nodes as they really sit in memory, see lists::viz.
+#[cfg(feature = "viz")]
+impl<T: std::fmt::Debug> List<T> {
+    pub fn to_dot(&self) -> String {
+        use lists::viz::{self, Dot};
+
+        let mut dot = Dot::new("second::List");
+        dot.handle("list", "List");
+        let (mut from, mut label) = ("list".to_string(), "head");
+        let mut link = self.head.as_deref();
+        while let Some(node) = link {
+            let id = viz::id(node);
+            dot.node(&id, &[format!("elem: {}", viz::elem(&node.elem))]);
+            dot.edge(&from, &id, label);
+            (from, label) = (id, "next");
+            link = node.next.as_deref();
+        }
+        dot.finish()
+    }
+}
+
+impl<T> Stack<T> for List<T> {
+    type Peek<'a>
+        = &'a T
+    where
+        T: 'a;
+
+    fn push(&mut self, elem: T) {
+        List::push(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        List::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        List::peek(self)
+    }
 }
@@ -1,11 +1,69 @@
 #![allow(dead_code)]
+use crate::traits::{Deque, Queue, Stack};
+use std::alloc::{self as global_alloc, Layout};
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
 use std::marker::PhantomData;
 use std::ptr::NonNull;
 
-pub struct LinkedList<T> {
+/// Where the list gets its nodes from. This is a cut down version of the
+/// unstable `std::alloc::Allocator`, so it works on stable.
+///
+/// # Safety
+///
+/// Memory returned by `allocate` must stay valid until it is passed to
+/// `deallocate`. Clones of an allocator must be able to free each other's
+/// memory, because lists hand nodes around between clones (split_off,
+/// append, splice...). `same_allocator` may only return true for two values
+/// that can free each other's memory.
+pub unsafe trait Allocator {
+    /// Returns None if the allocation failed.
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// # Safety
+    ///
+    /// `ptr` must come from `allocate` on this allocator, a clone of it or an
+    /// allocator `same_allocator` accepts, with the same `layout`, and must
+    /// not be used afterwards.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// Whether `self` can free memory that `other` allocated. Lists check
+    /// this before they take in another list's nodes.
+    fn same_allocator(&self, other: &Self) -> bool;
+}
+
+// The default allocator, just forwards to the global allocator like Box does.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // Nodes always have two pointers in them, so layout is never zero sized.
+        debug_assert!(layout.size() != 0);
+        NonNull::new(unsafe { global_alloc::alloc(layout) })
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        global_alloc::dealloc(ptr.as_ptr(), layout)
+    }
+
+    fn same_allocator(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+pub struct LinkedList<T, A: Allocator = Global> {
     front: Link<T>,
     back: Link<T>,
     len: usize,
+    alloc: A,
+    // Spare nodes with no element in them, chained through their `back` links.
+    // Only filled when `recycle` is on or by `reserve`.
+    pool: Link<T>,
+    pool_len: usize,
+    recycle: bool,
     _boo: PhantomData<T>,
 }
 
@@ -17,24 +75,185 @@ struct Node<T> {
     elem: T,
 }
 
+// Iter and IterMut keep their own front/back cursors and a count of what is
+// left between them, so both ends can be consumed until they meet.
+pub struct Iter<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a T>,
+}
+
+pub struct IterMut<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+pub struct IntoIter<T, A: Allocator = Global> {
+    list: LinkedList<T, A>,
+}
+
+// Lazily walks the list, unlinking and yielding every element the predicate
+// picks. Whatever isn't visited before it's dropped stays in the list.
+pub struct ExtractIf<'a, T, F, A: Allocator = Global> {
+    list: &'a mut LinkedList<T, A>,
+    cur: Link<T>,
+    pred: F,
+}
+
+// A cursor sits either on a real node or on the "ghost" that lives between
+// back and front, which is where `index` is None. Walking off either end
+// lands on the ghost, and walking past the ghost wraps around to the other end.
+pub struct CursorMut<'a, T, A: Allocator = Global> {
+    list: &'a mut LinkedList<T, A>,
+    cur: Link<T>,
+    index: Option<usize>,
+}
+
 impl<T> LinkedList<T> {
     pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    pub fn new_in(alloc: A) -> Self {
         Self {
             front: None,
             back: None,
             len: 0,
+            alloc,
+            pool: None,
+            pool_len: 0,
+            recycle: false,
             _boo: PhantomData,
         }
     }
 
-    pub fn push_front(&mut self, elem: T) {
-        // SAFETY: it's a linked-list, what do you want?
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    // With recycling on, nodes freed by pops and removals go into a pool that
+    // later pushes take from, instead of going back to the allocator.
+    pub fn set_recycling(&mut self, recycle: bool) {
+        self.recycle = recycle;
+    }
+
+    pub fn is_recycling(&self) -> bool {
+        self.recycle
+    }
+
+    // How many elements fit before we have to ask the allocator again.
+    pub fn capacity(&self) -> usize {
+        self.len + self.pool_len
+    }
+
+    // Fills the pool so at least `additional` more elements can be pushed
+    // without allocating. Works whether or not recycling is on.
+    pub fn reserve(&mut self, additional: usize) {
+        let layout = Layout::new::<Node<T>>();
+        while self.pool_len < additional {
+            let Some(ptr) = self.alloc.allocate(layout) else {
+                global_alloc::handle_alloc_error(layout)
+            };
+            let node = ptr.cast::<Node<T>>();
+            unsafe {
+                // Only the link is initialized, elem stays garbage until reuse.
+                std::ptr::addr_of_mut!((*node.as_ptr()).back).write(self.pool);
+            }
+            self.pool = Some(node);
+            self.pool_len += 1;
+        }
+    }
+
+    // Gives every pooled node back to the allocator.
+    pub fn shrink_to_fit(&mut self) {
+        let layout = Layout::new::<Node<T>>();
+        while let Some(node) = self.pool {
+            unsafe {
+                self.pool = (*node.as_ptr()).back;
+                self.alloc.deallocate(node.cast::<u8>(), layout);
+            }
+            self.pool_len -= 1;
+        }
+        debug_assert!(self.pool_len == 0);
+    }
+
+    // Every node comes from here instead of Box::new, so it's the only place
+    // that has to know about the allocator (together with free_node).
+    fn alloc_node(&mut self, elem: T) -> NonNull<Node<T>> {
+        let node = if let Some(node) = self.pool {
+            // Reuse a pooled node, its old contents were already moved out.
+            self.pool = unsafe { (*node.as_ptr()).back };
+            self.pool_len -= 1;
+            node
+        } else {
+            let layout = Layout::new::<Node<T>>();
+            let Some(ptr) = self.alloc.allocate(layout) else {
+                global_alloc::handle_alloc_error(layout)
+            };
+            ptr.cast::<Node<T>>()
+        };
         unsafe {
-            let new = NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+            node.as_ptr().write(Node {
                 front: None,
                 back: None,
                 elem,
-            })));
+            });
+        }
+        node
+    }
+
+    // Moves the element out of `node` and gives the memory back to the
+    // allocator, or to the pool if we're recycling.
+    // SAFETY: `node` must come from alloc_node and already be unlinked,
+    // nothing may touch it afterwards.
+    unsafe fn free_node(&mut self, node: NonNull<Node<T>>) -> T {
+        let elem = std::ptr::read(&(*node.as_ptr()).elem);
+        if self.recycle {
+            (*node.as_ptr()).back = self.pool;
+            self.pool = Some(node);
+            self.pool_len += 1;
+        } else {
+            self.alloc
+                .deallocate(node.cast::<u8>(), Layout::new::<Node<T>>());
+        }
+        elem
+    }
+
+    // Builds a list around nodes cut out of this one. The caller has to fix
+    // up self. The pool stays with self, only the recycling mode carries over.
+    fn sibling(&self, front: Link<T>, back: Link<T>, len: usize) -> Self
+    where
+        A: Clone,
+    {
+        let mut list = Self::new_in(self.alloc.clone());
+        list.front = front;
+        list.back = back;
+        list.len = len;
+        list.recycle = self.recycle;
+        list
+    }
+
+    // Moves all the nodes into a new list, leaving self empty but with its pool.
+    fn take_nodes(&mut self) -> Self
+    where
+        A: Clone,
+    {
+        let list = self.sibling(self.front, self.back, self.len);
+        self.front = None;
+        self.back = None;
+        self.len = 0;
+        list
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        // SAFETY: it's a linked-list, what do you want?
+        unsafe {
+            let new = self.alloc_node(elem);
             if let Some(old) = self.front {
                 // Put the new front before the old one
                 (*old.as_ptr()).front = Some(new);
@@ -47,6 +266,22 @@ impl<T> LinkedList<T> {
         }
     }
 
+    pub fn push_back(&mut self, elem: T) {
+        // SAFETY: mirror image of push_front, swap every front and back.
+        unsafe {
+            let new = self.alloc_node(elem);
+            if let Some(old) = self.back {
+                // Put the new back after the old one
+                (*old.as_ptr()).back = Some(new);
+                (*new.as_ptr()).front = Some(old);
+            } else {
+                self.front = Some(new);
+            }
+            self.back = Some(new);
+            self.len += 1;
+        }
+    }
+
     pub fn pop_front(&mut self) -> Option<T> {
         unsafe {
             // Only have to do stuff if there is a front node to pop.
@@ -54,13 +289,8 @@ impl<T> LinkedList<T> {
             // because everything is Copy and there are no dtors that will
             // run if we mess up... right? :) Riiiight? :)))
             self.front.map(|node| {
-                // Bring the Box back to life so we can move out its value and
-                // Drop it (Box continues to magically understand this for us).
-                let boxed_node = Box::from_raw(node.as_ptr());
-                let result = boxed_node.elem;
-
                 // Make the next node into the new front.
-                self.front = boxed_node.back;
+                self.front = (*node.as_ptr()).back;
                 if let Some(new) = self.front {
                     // Cleanup its reference to the removed node
                     (*new.as_ptr()).front = None;
@@ -71,8 +301,29 @@ impl<T> LinkedList<T> {
                 }
 
                 self.len -= 1;
-                result
-                // Box gets implicitly freed here, knows there is no T.
+                // Move the value out and hand the node back to the allocator.
+                self.free_node(node)
+            })
+        }
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        unsafe {
+            // Same as pop_front, but walking in from the other end.
+            self.back.map(|node| {
+                // Make the previous node into the new back.
+                self.back = (*node.as_ptr()).front;
+                if let Some(new) = self.back {
+                    // Cleanup its reference to the removed node
+                    (*new.as_ptr()).back = None;
+                } else {
+                    // If the back is now null, then this list is now empty!
+                    debug_assert!(self.len == 1);
+                    self.front = None;
+                }
+
+                self.len -= 1;
+                self.free_node(node)
             })
         }
     }
@@ -81,6 +332,15 @@ impl<T> LinkedList<T> {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // With recycling on the nodes stay around in the pool.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
     pub fn front(&self) -> Option<&T> {
         unsafe { self.front.map(|node| &(*node.as_ptr()).elem) }
     }
@@ -88,11 +348,1177 @@ impl<T> LinkedList<T> {
     pub fn front_mut(&mut self) -> Option<&mut T> {
         unsafe { self.front.map(|node| &mut (*node.as_ptr()).elem) }
     }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe { self.back.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.back.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    // The cursor starts on the ghost, so the first move_next lands on front.
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut {
+            list: self,
+            cur: None,
+            index: None,
+        }
+    }
+
+    // Index based access for when a cursor is overkill. All of these walk in
+    // from whichever end is closer, so they cost at most len / 2 steps.
+    pub fn get(&self, at: usize) -> Option<&T> {
+        unsafe { self.node_at(at).map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn get_mut(&mut self, at: usize) -> Option<&mut T> {
+        unsafe { self.node_at(at).map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn insert(&mut self, at: usize, elem: T) {
+        assert!(
+            at <= self.len,
+            "insertion index (is {}) should be <= len (is {})",
+            at,
+            self.len
+        );
+        if let Some(node) = self.node_at(at) {
+            unsafe {
+                let new = self.alloc_node(elem);
+                self.link_before(new, node);
+                self.len += 1;
+            }
+        } else {
+            // at == len, there is no node to go in front of
+            self.push_back(elem);
+        }
+    }
+
+    pub fn remove(&mut self, at: usize) -> Option<T> {
+        self.node_at(at)
+            .map(|node| unsafe { self.remove_node(node) })
+    }
+
+    // Moves all of other's nodes onto our back. Only the two boundary nodes
+    // get relinked, so this is O(1) no matter how long either list is. The
+    // nodes will be freed by our allocator, so other has to use the same one.
+    pub fn append(&mut self, other: &mut Self) {
+        assert!(
+            self.alloc.same_allocator(&other.alloc),
+            "Cannot append a list from a different allocator"
+        );
+        // Take other's pointers so it is left as a valid empty list.
+        let (Some(other_front), Some(other_back)) = (other.front.take(), other.back.take()) else {
+            return;
+        };
+        unsafe {
+            if let Some(back) = self.back {
+                (*back.as_ptr()).back = Some(other_front);
+                (*other_front.as_ptr()).front = Some(back);
+            } else {
+                // We're empty, other's front becomes ours
+                self.front = Some(other_front);
+            }
+        }
+        self.back = Some(other_back);
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    // Splits the list in two at the given index. Returns everything from
+    // `at` onwards, self keeps [0, at).
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        A: Clone,
+    {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+        if at == 0 {
+            return self.take_nodes();
+        }
+        if at == self.len {
+            return self.sibling(None, None, 0);
+        }
+
+        // 0 < at < len, so both the split node and the one before it exist.
+        let split = self.node_at(at).unwrap();
+        unsafe {
+            let prev = (*split.as_ptr()).front.unwrap();
+            (*prev.as_ptr()).back = None;
+            (*split.as_ptr()).front = None;
+
+            let output = self.sibling(Some(split), self.back, self.len - at);
+            self.back = Some(prev);
+            self.len = at;
+            output
+        }
+    }
+
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(Ord::cmp);
+    }
+
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    // Bottom-up merge sort that only ever relinks nodes: no allocation and the
+    // elements never move in memory. Merging two neighbouring runs is done by
+    // unlinking each node of the right run that sorts strictly before the
+    // current left node and relinking it in front of that node. Equal nodes
+    // never jump, which keeps the sort stable.
+    //
+    // Every relink is finished before `compare` is called again, so the list is
+    // well formed whenever user code runs. If `compare` panics we just unwind
+    // and the list is left partially sorted with every element still in it.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        let mut width = 1;
+        while width < self.len {
+            // Each pass merges pairs of runs of `width` nodes, front to back.
+            let mut start = self.front;
+            while let Some(mut left) = start {
+                // Find the right run. No right run means this pass is done.
+                let mut right = Some(left);
+                for _ in 0..width {
+                    right = right.and_then(|node| unsafe { (*node.as_ptr()).back });
+                }
+                let mut left_rem = width;
+                let mut right_rem = width;
+
+                while left_rem > 0 && right_rem > 0 {
+                    let Some(r) = right else { break };
+                    unsafe {
+                        if compare(&(*r.as_ptr()).elem, &(*left.as_ptr()).elem) == Ordering::Less {
+                            right = (*r.as_ptr()).back;
+                            right_rem -= 1;
+                            self.unlink_node(r);
+                            self.link_before(r, left);
+                        } else {
+                            // left was in front of right, so there is a next node
+                            left = (*left.as_ptr()).back.unwrap();
+                            left_rem -= 1;
+                        }
+                    }
+                }
+
+                // Whatever is left of the right run is already in place, skip it.
+                for _ in 0..right_rem {
+                    right = right.and_then(|node| unsafe { (*node.as_ptr()).back });
+                }
+                start = right;
+            }
+            width *= 2;
+        }
+    }
+
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    // The node is only unlinked after `f` has returned, so a panicking `f`
+    // leaves the list well formed with the remaining elements untouched.
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let mut cur = self.front;
+        while let Some(node) = cur {
+            unsafe {
+                cur = (*node.as_ptr()).back;
+                if !f(&mut (*node.as_ptr()).elem) {
+                    self.remove_node(node);
+                }
+            }
+        }
+    }
+
+    pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, pred: F) -> ExtractIf<'_, T, F, A> {
+        ExtractIf {
+            cur: self.front,
+            list: self,
+            pred,
+        }
+    }
+
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    // Same contract as Vec::dedup_by: `same_bucket` gets the candidate first and
+    // the last kept element second, and the candidate is removed on true.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        let Some(mut kept) = self.front else { return };
+        unsafe {
+            let mut cur = (*kept.as_ptr()).back;
+            while let Some(node) = cur {
+                cur = (*node.as_ptr()).back;
+                if same_bucket(&mut (*node.as_ptr()).elem, &mut (*kept.as_ptr()).elem) {
+                    self.remove_node(node);
+                } else {
+                    kept = node;
+                }
+            }
+        }
+    }
+
+    // Unlinks `node`, frees it and hands back its element.
+    // SAFETY: `node` must be in this list.
+    unsafe fn remove_node(&mut self, node: NonNull<Node<T>>) -> T {
+        self.unlink_node(node);
+        self.len -= 1;
+        self.free_node(node)
+    }
+
+    // Takes `node` out of the list, leaving its own links dangling.
+    // SAFETY: `node` must be in this list.
+    unsafe fn unlink_node(&mut self, node: NonNull<Node<T>>) {
+        let prev = (*node.as_ptr()).front;
+        let next = (*node.as_ptr()).back;
+        if let Some(prev) = prev {
+            (*prev.as_ptr()).back = next;
+        } else {
+            self.front = next;
+        }
+        if let Some(next) = next {
+            (*next.as_ptr()).front = prev;
+        } else {
+            self.back = prev;
+        }
+    }
+
+    // Links a detached `node` back in right before `at`.
+    // SAFETY: `at` must be in this list and `node` must be unlinked from it.
+    unsafe fn link_before(&mut self, node: NonNull<Node<T>>, at: NonNull<Node<T>>) {
+        let prev = (*at.as_ptr()).front;
+        (*node.as_ptr()).front = prev;
+        (*node.as_ptr()).back = Some(at);
+        (*at.as_ptr()).front = Some(node);
+        if let Some(prev) = prev {
+            (*prev.as_ptr()).back = Some(node);
+        } else {
+            self.front = Some(node);
+        }
+    }
+
+    // Finds the node at `at`, walking in from whichever end is closer.
+    fn node_at(&self, at: usize) -> Link<T> {
+        if at >= self.len {
+            return None;
+        }
+        unsafe {
+            if at < self.len / 2 {
+                let mut node = self.front;
+                for _ in 0..at {
+                    node = (*node?.as_ptr()).back;
+                }
+                node
+            } else {
+                let mut node = self.back;
+                for _ in 0..self.len - 1 - at {
+                    node = (*node?.as_ptr()).front;
+                }
+                node
+            }
+        }
+    }
 } // impl<T> LinkedList<T>
 
-impl<T> Drop for LinkedList<T> {
+#[cfg(any(test, feature = "debug-invariants"))]
+impl<T, A: Allocator> LinkedList<T, A> {
+    // Walks the whole list and panics on the first broken link it finds.
+    pub fn check_invariants(&self) {
+        unsafe {
+            let mut prev: Link<T> = None;
+            let mut cur = self.front;
+            let mut count = 0;
+            while let Some(node) = cur {
+                assert!(
+                    count < self.len,
+                    "more than len ({}) nodes reachable from front, node {:?} is extra",
+                    self.len,
+                    node
+                );
+                let front = (*node.as_ptr()).front;
+                assert!(
+                    front == prev,
+                    "node {} ({:?}) has front link {:?}, but the node before it is {:?}",
+                    count,
+                    node,
+                    front,
+                    prev
+                );
+                prev = cur;
+                cur = (*node.as_ptr()).back;
+                count += 1;
+            }
+            assert!(
+                count == self.len,
+                "len is {} but only {} nodes are reachable from front",
+                self.len,
+                count
+            );
+            assert!(
+                self.back == prev,
+                "back is {:?} but the last node reachable from front is {:?}",
+                self.back,
+                prev
+            );
+            // A back node with a back link would have been walked above, so the
+            // only end left to check is an empty list with a stray pointer.
+            assert!(
+                self.front.is_some() == self.back.is_some(),
+                "front is {:?} but back is {:?}",
+                self.front,
+                self.back
+            );
+
+            let mut pooled = 0;
+            let mut cur = self.pool;
+            while let Some(node) = cur {
+                assert!(
+                    pooled < self.pool_len,
+                    "more than pool_len ({}) nodes in the pool, node {:?} is extra",
+                    self.pool_len,
+                    node
+                );
+                cur = (*node.as_ptr()).back;
+                pooled += 1;
+            }
+            assert!(
+                pooled == self.pool_len,
+                "pool_len is {} but the pool holds {} nodes",
+                self.pool_len,
+                pooled
+            );
+        }
+    }
+}
+
+impl<T, A: Allocator> Drop for LinkedList<T, A> {
     fn drop(&mut self) {
+        // If an element's drop panics, the guard picks up where we left off
+        // while unwinding, so the rest of the nodes and the pool still get
+        // freed. Either way it's the guard that empties the pool.
+        struct DropGuard<'a, T, A: Allocator>(&'a mut LinkedList<T, A>);
+
+        impl<T, A: Allocator> Drop for DropGuard<'_, T, A> {
+            fn drop(&mut self) {
+                while self.0.pop_front().is_some() {}
+                self.0.shrink_to_fit();
+            }
+        }
+
+        // No point in pooling nodes we're about to free anyway
+        self.recycle = false;
+        let guard = DropGuard(self);
         // Pop until we have to stop
-        while let Some(_) = self.pop_front() {}
+        while guard.0.pop_front().is_some() {}
+    }
+}
+
+// NonNull opts us out of Send and Sync, so we opt back in with the same bounds
+// std's LinkedList uses. Sharing a list (or an Iter) only hands out &T, so Sync
+// is enough there; anything that can hand out &mut T or T needs Send.
+unsafe impl<T: Send, A: Allocator + Send> Send for LinkedList<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for LinkedList<T, A> {}
+
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
+unsafe impl<'a, T: Send, A: Allocator + Send> Send for CursorMut<'a, T, A> {}
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Sync for CursorMut<'a, T, A> {}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for LinkedList<T, A> {
+    fn clone(&self) -> Self {
+        let mut new_list = Self::new_in(self.alloc.clone());
+        for item in self {
+            new_list.push_back(item.clone());
+        }
+        new_list
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for LinkedList<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy, A: Allocator> Extend<&'a T> for LinkedList<T, A> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for LinkedList<T> {
+    fn from(arr: [T; N]) -> Self {
+        arr.into_iter().collect()
+    }
+}
+
+// Graphviz picture of the nodes, see lists::viz. Spare nodes in the recycling
+// pool are drawn too, chained off a separate handle.
+#[cfg(feature = "viz")]
+impl<T: Debug, A: Allocator> LinkedList<T, A> {
+    pub fn to_dot(&self) -> String {
+        use lists::viz::{self, Dot};
+
+        let mut dot = Dot::new("sixth::LinkedList");
+        dot.handle("list", &format!("LinkedList (len {})", self.len));
+        if let Some(back) = self.back {
+            dot.back_edge("list", &viz::id(back.as_ptr()), "back");
+        }
+        let (mut from, mut label) = ("list".to_string(), "front");
+        let mut link = self.front;
+        while let Some(node) = link {
+            let id = viz::id(node.as_ptr());
+            unsafe {
+                dot.node(
+                    &id,
+                    &[format!("elem: {}", viz::elem(&(*node.as_ptr()).elem))],
+                );
+                if let Some(prev) = (*node.as_ptr()).front {
+                    dot.back_edge(&id, &viz::id(prev.as_ptr()), "front");
+                }
+                link = (*node.as_ptr()).back;
+            }
+            dot.edge(&from, &id, label);
+            (from, label) = (id, "back");
+        }
+
+        if self.pool.is_some() {
+            dot.handle("pool", &format!("pool (len {})", self.pool_len));
+            let mut from = "pool".to_string();
+            let mut link = self.pool;
+            while let Some(node) = link {
+                let id = viz::id(node.as_ptr());
+                // Pooled nodes hold no element, only their `back` link is set
+                dot.node(&id, &["(empty)".to_string()]);
+                dot.edge(&from, &id, "back");
+                from = id;
+                link = unsafe { (*node.as_ptr()).back };
+            }
+        }
+        dot.finish()
+    }
+}
+
+// The front doubles as the top of the stack.
+impl<T, A: Allocator> Stack<T> for LinkedList<T, A> {
+    type Peek<'a>
+        = &'a T
+    where
+        Self: 'a;
+
+    fn push(&mut self, elem: T) {
+        LinkedList::push_front(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        LinkedList::pop_front(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.front()
     }
 }
+
+impl<T, A: Allocator> Queue<T> for LinkedList<T, A> {
+    type Peek<'a>
+        = &'a T
+    where
+        Self: 'a;
+
+    fn push_back(&mut self, elem: T) {
+        LinkedList::push_back(self, elem)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        LinkedList::pop_front(self)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        self.front()
+    }
+}
+
+impl<T, A: Allocator> Deque<T> for LinkedList<T, A> {
+    fn push_front(&mut self, elem: T) {
+        LinkedList::push_front(self, elem)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        LinkedList::pop_back(self)
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        self.back()
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for LinkedList<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, A: Allocator> PartialEq for LinkedList<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        // Comparing len first is O(1) and saves a walk for most mismatches
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for LinkedList<T, A> {}
+
+impl<T: PartialOrd, A: Allocator> PartialOrd for LinkedList<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T: Ord, A: Allocator> Ord for LinkedList<T, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+impl<T: Hash, A: Allocator> Hash for LinkedList<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hashing the len keeps lists of lists from colliding,
+        // e.g. [[1], [2, 3]] and [[1, 2], [3]].
+        self.len().hash(state);
+        for item in self {
+            item.hash(state);
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a LinkedList<T, A> {
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Checking self.front == self.back won't work here, it would stop one
+        // element too early. The remaining len tells us when the cursors met.
+        if self.len > 0 {
+            self.front.map(|node| unsafe {
+                self.len -= 1;
+                self.front = (*node.as_ptr()).back;
+                &(*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                self.len -= 1;
+                self.back = (*node.as_ptr()).front;
+                &(*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut LinkedList<T, A> {
+    type IntoIter = IterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Same as Iter, the len guarantees we never hand out a node twice.
+        if self.len > 0 {
+            self.front.map(|node| unsafe {
+                self.len -= 1;
+                self.front = (*node.as_ptr()).back;
+                &mut (*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                self.len -= 1;
+                self.back = (*node.as_ptr()).front;
+                &mut (*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T> FusedIterator for IterMut<'a, T> {}
+
+impl<T, A: Allocator> IntoIterator for LinkedList<T, A> {
+    type IntoIter = IntoIter<T, A>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
+
+impl<'a, T, F: FnMut(&mut T) -> bool, A: Allocator> Iterator for ExtractIf<'a, T, F, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.cur {
+            unsafe {
+                // Step past the node before the predicate can decide to remove it
+                self.cur = (*node.as_ptr()).back;
+                if (self.pred)(&mut (*node.as_ptr()).elem) {
+                    return Some(self.list.remove_node(node));
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Can't know how many will match, but never more than are left
+        (0, Some(self.list.len))
+    }
+}
+
+impl<'a, T, F: FnMut(&mut T) -> bool, A: Allocator> FusedIterator for ExtractIf<'a, T, F, A> {}
+
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                // We're on a real element, go to its next (back)
+                self.cur = (*cur.as_ptr()).back;
+                if self.cur.is_some() {
+                    *self.index.as_mut().unwrap() += 1;
+                } else {
+                    // We just walked to the ghost, no more index
+                    self.index = None;
+                }
+            }
+        } else if !self.list.is_empty() {
+            // We're at the ghost, and there is a real front, so move to it!
+            self.cur = self.list.front;
+            self.index = Some(0)
+        } else {
+            // We're at the ghost, but that's the only element... do nothing.
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                // We're on a real element, go to its previous (front)
+                self.cur = (*cur.as_ptr()).front;
+                if self.cur.is_some() {
+                    *self.index.as_mut().unwrap() -= 1;
+                } else {
+                    // We just walked to the ghost, no more index
+                    self.index = None;
+                }
+            }
+        } else if !self.list.is_empty() {
+            // We're at the ghost, and there is a real back, so move to it!
+            self.cur = self.list.back;
+            self.index = Some(self.list.len - 1)
+        } else {
+            // We're at the ghost, but that's the only element... do nothing.
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.cur.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        unsafe {
+            let next = if let Some(cur) = self.cur {
+                // Normal case, try to follow the cur node's back pointer
+                (*cur.as_ptr()).back
+            } else {
+                // Ghost case, try to use the list's front pointer
+                self.list.front
+            };
+
+            // Yield the element if the next node exists
+            next.map(|node| &mut (*node.as_ptr()).elem)
+        }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        unsafe {
+            let prev = if let Some(cur) = self.cur {
+                // Normal case, try to follow the cur node's front pointer
+                (*cur.as_ptr()).front
+            } else {
+                // Ghost case, try to use the list's back pointer
+                self.list.back
+            };
+
+            // Yield the element if the prev node exists
+            prev.map(|node| &mut (*node.as_ptr()).elem)
+        }
+    }
+
+    // Inserting before the ghost means inserting at the back of the list.
+    pub fn insert_before(&mut self, elem: T) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                if let Some(prev) = (*cur.as_ptr()).front {
+                    // General case, link the new node in between prev and cur
+                    let new = self.list.alloc_node(elem);
+                    (*new.as_ptr()).front = Some(prev);
+                    (*new.as_ptr()).back = Some(cur);
+                    (*prev.as_ptr()).back = Some(new);
+                    (*cur.as_ptr()).front = Some(new);
+                    self.list.len += 1;
+                } else {
+                    // cur is the front, so this is just a push_front
+                    self.list.push_front(elem);
+                }
+                // Everything from cur onwards shifted by one
+                *self.index.as_mut().unwrap() += 1;
+            }
+        } else {
+            self.list.push_back(elem);
+        }
+    }
+
+    // Inserting after the ghost means inserting at the front of the list.
+    pub fn insert_after(&mut self, elem: T) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                if let Some(next) = (*cur.as_ptr()).back {
+                    // General case, link the new node in between cur and next
+                    let new = self.list.alloc_node(elem);
+                    (*new.as_ptr()).front = Some(cur);
+                    (*new.as_ptr()).back = Some(next);
+                    (*cur.as_ptr()).back = Some(new);
+                    (*next.as_ptr()).front = Some(new);
+                    self.list.len += 1;
+                } else {
+                    // cur is the back, so this is just a push_back
+                    self.list.push_back(elem);
+                }
+            }
+        } else {
+            self.list.push_front(elem);
+        }
+    }
+
+    // Unlinks the current node and moves the cursor onto the next one (or the
+    // ghost if it was the back). Does nothing on the ghost.
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.cur.map(|node| unsafe {
+            let next = (*node.as_ptr()).back;
+            self.cur = next;
+            if next.is_none() {
+                // We removed the back, so we walked onto the ghost
+                self.index = None;
+            }
+            self.list.remove_node(node)
+        })
+    }
+
+    pub fn split_before(&mut self) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        // We have this:
+        //
+        //     list.front -> A <-> B <-> C <-> D <- list.back
+        //                               ^
+        //                              cur
+        //
+        // And we want to produce this:
+        //
+        //     list.front -> C <-> D <- list.back
+        //                   ^
+        //                  cur
+        //
+        //    return.front -> A <-> B <- return.back
+        //
+        if let Some(cur) = self.cur {
+            // We are pointing at a real element, so the list is non-empty.
+            unsafe {
+                let old_len = self.list.len;
+                let old_idx = self.index.unwrap();
+                let prev = (*cur.as_ptr()).front;
+
+                // Everything before cur goes to the output. If cur is already
+                // the front that is nothing at all, and we must not hand out
+                // list.front or both lists would own the same node.
+                let output = if let Some(prev) = prev {
+                    (*cur.as_ptr()).front = None;
+                    (*prev.as_ptr()).back = None;
+                    self.list.sibling(self.list.front, Some(prev), old_idx)
+                } else {
+                    self.list.sibling(None, None, 0)
+                };
+
+                self.list.front = Some(cur);
+                self.list.len = old_len - old_idx;
+                self.index = Some(0);
+                output
+            }
+        } else {
+            // We're at the ghost, just move everything out into the output.
+            // No other state needs to be changed.
+            self.list.take_nodes()
+        }
+    }
+
+    pub fn split_after(&mut self) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        // We have this:
+        //
+        //     list.front -> A <-> B <-> C <-> D <- list.back
+        //                         ^
+        //                        cur
+        //
+        // And we want to produce this:
+        //
+        //     list.front -> A <-> B <- list.back
+        //                         ^
+        //                        cur
+        //
+        //    return.front -> C <-> D <- return.back
+        //
+        if let Some(cur) = self.cur {
+            // We are pointing at a real element, so the list is non-empty.
+            unsafe {
+                let old_len = self.list.len;
+                let old_idx = self.index.unwrap();
+                let next = (*cur.as_ptr()).back;
+
+                // Same as split_before: if cur is the back, nothing is split off.
+                let output = if let Some(next) = next {
+                    (*cur.as_ptr()).back = None;
+                    (*next.as_ptr()).front = None;
+                    self.list
+                        .sibling(Some(next), self.list.back, old_len - old_idx - 1)
+                } else {
+                    self.list.sibling(None, None, 0)
+                };
+
+                self.list.back = Some(cur);
+                self.list.len = old_idx + 1;
+                output
+            }
+        } else {
+            // We're at the ghost, just move everything out into the output.
+            // No other state needs to be changed.
+            self.list.take_nodes()
+        }
+    }
+
+    // input's nodes become ours and are freed by our allocator, so input has
+    // to use the same one.
+    pub fn splice_before(&mut self, mut input: LinkedList<T, A>) {
+        assert!(
+            self.list.alloc.same_allocator(&input.alloc),
+            "Cannot splice in a list from a different allocator"
+        );
+        // We have this:
+        //
+        // input.front -> 1 <-> 2 <- input.back
+        //
+        // list.front -> A <-> B <-> C <- list.back
+        //                     ^
+        //                    cur
+        //
+        // Becoming this:
+        //
+        // list.front -> A <-> 1 <-> 2 <-> B <-> C <- list.back
+        //                                 ^
+        //                                cur
+        //
+        unsafe {
+            // We `take` the input's pointers rather than `mem::forget` it, so
+            // input is a valid empty list when it gets dropped at the end.
+            if input.is_empty() {
+                // Input is empty, do nothing.
+            } else if let Some(cur) = self.cur {
+                // Both lists are non-empty
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                if let Some(prev) = (*cur.as_ptr()).front {
+                    // General Case, no boundaries, just internal fixups
+                    (*prev.as_ptr()).back = Some(in_front);
+                    (*in_front.as_ptr()).front = Some(prev);
+                } else {
+                    // No prev, we're prepending to the front
+                    self.list.front = Some(in_front);
+                }
+                (*cur.as_ptr()).front = Some(in_back);
+                (*in_back.as_ptr()).back = Some(cur);
+                // Index moves forward by input length
+                *self.index.as_mut().unwrap() += input.len;
+            } else if let Some(back) = self.list.back {
+                // We're on the ghost but non-empty, append to the back
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                (*back.as_ptr()).back = Some(in_front);
+                (*in_front.as_ptr()).front = Some(back);
+                self.list.back = Some(in_back);
+            } else {
+                // We're empty, adopt the input's nodes, remain on the ghost.
+                // Swapping the whole lists would throw away our pool.
+                self.list.front = input.front.take();
+                self.list.back = input.back.take();
+            }
+
+            self.list.len += input.len;
+            // Not necessary but Polite To Do
+            input.len = 0;
+
+            // Input dropped here
+        }
+    }
+
+    // input's nodes become ours and are freed by our allocator, so input has
+    // to use the same one.
+    pub fn splice_after(&mut self, mut input: LinkedList<T, A>) {
+        assert!(
+            self.list.alloc.same_allocator(&input.alloc),
+            "Cannot splice in a list from a different allocator"
+        );
+        // We have this:
+        //
+        // input.front -> 1 <-> 2 <- input.back
+        //
+        // list.front -> A <-> B <-> C <- list.back
+        //                     ^
+        //                    cur
+        //
+        // Becoming this:
+        //
+        // list.front -> A <-> B <-> 1 <-> 2 <-> C <- list.back
+        //                     ^
+        //                    cur
+        //
+        unsafe {
+            if input.is_empty() {
+                // Input is empty, do nothing.
+            } else if let Some(cur) = self.cur {
+                // Both lists are non-empty
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                if let Some(next) = (*cur.as_ptr()).back {
+                    // General Case, no boundaries, just internal fixups
+                    (*next.as_ptr()).front = Some(in_back);
+                    (*in_back.as_ptr()).back = Some(next);
+                } else {
+                    // No next, we're appending to the back
+                    self.list.back = Some(in_back);
+                }
+                (*cur.as_ptr()).back = Some(in_front);
+                (*in_front.as_ptr()).front = Some(cur);
+                // Index doesn't change
+            } else if let Some(front) = self.list.front {
+                // We're on the ghost but non-empty, prepend to the front
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                (*front.as_ptr()).front = Some(in_back);
+                (*in_back.as_ptr()).back = Some(front);
+                self.list.front = Some(in_front);
+            } else {
+                // We're empty, adopt the input's nodes, remain on the ghost.
+                // Swapping the whole lists would throw away our pool.
+                self.list.front = input.front.take();
+                self.list.back = input.back.take();
+            }
+
+            self.list.len += input.len;
+            // Not necessary but Polite To Do
+            input.len = 0;
+
+            // Input dropped here
+        }
+    }
+}
+
+// Never called, it only has to compile. If someone breaks Send/Sync or the
+// variance of any of these types, the build breaks instead of user code.
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_send<T: Send>() {}
+    fn is_sync<T: Sync>() {}
+
+    is_send::<LinkedList<i32>>();
+    is_sync::<LinkedList<i32>>();
+
+    is_send::<IntoIter<i32>>();
+    is_sync::<IntoIter<i32>>();
+
+    is_send::<Iter<i32>>();
+    is_sync::<Iter<i32>>();
+
+    is_send::<IterMut<i32>>();
+    is_sync::<IterMut<i32>>();
+
+    is_send::<CursorMut<i32>>();
+    is_sync::<CursorMut<i32>>();
+
+    fn linked_list_covariant<'a, T>(x: LinkedList<&'static T>) -> LinkedList<&'a T> {
+        x
+    }
+    fn iter_covariant<'i, 'a, T>(x: Iter<'i, &'static T>) -> Iter<'i, &'a T> {
+        x
+    }
+    fn into_iter_covariant<'a, T>(x: IntoIter<&'static T>) -> IntoIter<&'a T> {
+        x
+    }
+
+    /// ```compile_fail
+    /// use code::sixth::IterMut;
+    ///
+    /// fn iter_mut_covariant<'i, 'a, T>(x: IterMut<'i, &'static T>) -> IterMut<'i, &'a T> { x }
+    /// ```
+    fn iter_mut_invariant() {}
+
+    /// ```compile_fail
+    /// use code::sixth::CursorMut;
+    ///
+    /// fn cursor_mut_covariant<'i, 'a, T>(x: CursorMut<'i, &'static T>) -> CursorMut<'i, &'a T> { x }
+    /// ```
+    fn cursor_mut_invariant() {}
+
+    /// ```compile_fail,E0277
+    /// use code::sixth::LinkedList;
+    /// use std::rc::Rc;
+    ///
+    /// fn is_send<T: Send>() {}
+    /// is_send::<LinkedList<Rc<i32>>>();
+    /// ```
+    fn rc_list_not_send() {}
+}
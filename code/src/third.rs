@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 use std::rc::Rc;
 
+use crate::traits::Stack;
+
 // in this module an immutable thread not save stack will be implimented
 pub struct List<T> {
     head: Link<T>,
@@ -14,6 +16,7 @@ struct Node<T> {
 }
 
 impl<T> List<T> {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         List { head: None }
     }
@@ -21,7 +24,7 @@ impl<T> List<T> {
     pub fn prepend(&self, elem: T) -> List<T> {
         List {
             head: Some(Rc::new(Node {
-                elem: elem,
+                elem,
                 /* Lucky we our head = Option<Rc<Node<T>>>, this way we only incrementing
                  * counter on Rc. Relatively cheap operation. On each prepend previous
                  * List fat pointer Rc is returned.
@@ -93,3 +96,76 @@ impl<T> Drop for List<T> {
         }
     }
 }
+
+// Graphviz picture of the nodes, see lists::viz. Each node shows its Rc count,
+// which is how many lists (or other nodes) point at it.
+#[cfg(feature = "viz")]
+impl<T: std::fmt::Debug> List<T> {
+    pub fn to_dot(&self) -> String {
+        versions_to_dot(&[("list", self)])
+    }
+}
+
+// Draws several lists in one picture. Nodes are named after their addresses,
+// so a tail shared between versions shows up once, with an edge from every
+// version that reaches it.
+#[cfg(feature = "viz")]
+pub fn versions_to_dot<T: std::fmt::Debug>(versions: &[(&str, &List<T>)]) -> String {
+    use lists::viz::{self, Dot};
+
+    let mut dot = Dot::new("third::List");
+    for (name, list) in versions {
+        dot.handle(name, name);
+        let (mut from, mut label) = (name.to_string(), "head");
+        let mut link = list.head.as_ref();
+        while let Some(node) = link {
+            let id = viz::id(Rc::as_ptr(node));
+            let new = dot.node(
+                &id,
+                &[
+                    format!("elem: {}", viz::elem(&node.elem)),
+                    format!("rc: {}", Rc::strong_count(node)),
+                ],
+            );
+            dot.edge(&from, &id, label);
+            if !new {
+                // Another version already drew the rest of this tail
+                break;
+            }
+            (from, label) = (id, "next");
+            link = node.next.as_ref();
+        }
+    }
+    dot.finish()
+}
+
+// A persistent list can't just move its head out, other lists may share the
+// node. When we hold the last reference the element is moved out like any
+// other stack, otherwise it has to be cloned and the node left to the others.
+impl<T: Clone> Stack<T> for List<T> {
+    type Peek<'a>
+        = &'a T
+    where
+        T: 'a;
+
+    fn push(&mut self, elem: T) {
+        *self = self.prepend(elem);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.head.take().map(|node| match Rc::try_unwrap(node) {
+            Ok(node) => {
+                self.head = node.next;
+                node.elem
+            }
+            Err(shared) => {
+                self.head = shared.next.clone();
+                shared.elem.clone()
+            }
+        })
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.head()
+    }
+}
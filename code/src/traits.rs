@@ -0,0 +1,5 @@
+// Every chapter names its operations a little differently (push/prepend,
+// peek/head/peek_front, ...). The traits give them one vocabulary, so code
+// written against a Stack, Queue or Deque can switch lists by changing a type
+// parameter. They live in the lists crate so its lists can be switched in too.
+pub use lists::traits::{Deque, Queue, Stack};
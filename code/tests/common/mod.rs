@@ -0,0 +1,233 @@
+// Helpers shared by the test binaries: allocation and drop checks, and the
+// seeded random numbers for the randomized tests. Not every binary uses every
+// helper.
+#![allow(dead_code)]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::thread;
+
+// Forwards to the system allocator and keeps count of the allocations each
+// thread has live. The harness runs every test on its own thread, so counting
+// per thread keeps the tests from seeing each other's allocations.
+// Register it in a test binary with
+// `#[global_allocator] static ALLOC: CountingAlloc = CountingAlloc;`
+pub struct CountingAlloc;
+
+thread_local! {
+    // Const initialized and without a destructor, so touching it from inside
+    // the allocator never allocates itself.
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+}
+
+fn track(delta: isize) {
+    // Fails only while the thread is being torn down, nothing to count then
+    let _ = LIVE.try_with(|live| live.set(live.get() + delta));
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            track(1);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            track(1);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        track(-1);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    // A realloc moves an allocation, it doesn't add one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+// Allocations made and not yet freed by the current thread.
+pub fn live_allocations() -> isize {
+    LIVE.with(Cell::get)
+}
+
+// Runs `f` and checks that everything it allocated has been freed again by the
+// time it returns. Freeing more than was allocated is caught as well, that's
+// what a double free looks like from here.
+pub fn assert_leak_free<R>(what: &str, f: impl FnOnce() -> R) -> R {
+    let before = live_allocations();
+    let result = f();
+    let after = live_allocations();
+    assert_eq!(
+        after - before,
+        0,
+        "{} left {} allocations behind",
+        what,
+        after - before
+    );
+    result
+}
+
+// An element that records how many times it has been dropped. A drop count
+// above the number of elements made means something was dropped twice.
+// Clones count as elements of their own.
+#[derive(Clone, Debug)]
+pub struct DropCounter<'a> {
+    pub value: i32,
+    drops: &'a Cell<usize>,
+}
+
+impl<'a> DropCounter<'a> {
+    pub fn new(drops: &'a Cell<usize>, value: i32) -> Self {
+        DropCounter { value, drops }
+    }
+}
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+impl PartialEq for DropCounter<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+// Payload of every panic the element types below inject, so the panic hook
+// installed by `quiet_injected_panics` can tell them from real failures.
+pub struct InjectedPanic;
+
+// Keeps injected panics from printing. Printing goes through the captured
+// output buffer, which would show up as a live allocation in the middle of a
+// test. Anything else still reaches the default hook.
+pub fn quiet_injected_panics() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !info.payload().is::<InjectedPanic>() {
+                default(info);
+            }
+        }));
+    });
+}
+
+// Runs `f` and drops whatever it returns, one of which is expected to hit an
+// injected panic.
+pub fn expect_injected_panic<R>(f: impl FnOnce() -> R) {
+    match panic::catch_unwind(AssertUnwindSafe(|| drop(f()))) {
+        Ok(_) => panic!("expected an injected panic"),
+        Err(payload) => assert!(payload.is::<InjectedPanic>(), "a different panic"),
+    }
+}
+
+// An element that counts its drops like DropCounter, and panics on drop if
+// it's armed. It won't panic while the thread is already unwinding, that
+// would abort the whole test binary instead of failing one test.
+#[derive(Debug)]
+pub struct PanicOnDrop<'a> {
+    pub value: i32,
+    pub armed: bool,
+    drops: &'a Cell<usize>,
+}
+
+impl<'a> PanicOnDrop<'a> {
+    pub fn new(drops: &'a Cell<usize>, value: i32) -> Self {
+        PanicOnDrop {
+            value,
+            armed: false,
+            drops,
+        }
+    }
+
+    pub fn armed(drops: &'a Cell<usize>, value: i32) -> Self {
+        PanicOnDrop {
+            value,
+            armed: true,
+            drops,
+        }
+    }
+}
+
+impl Drop for PanicOnDrop<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+        if self.armed && !thread::panicking() {
+            panic::panic_any(InjectedPanic);
+        }
+    }
+}
+
+// An element that counts its drops, and panics when an armed one is cloned.
+// Clones are never armed themselves.
+#[derive(Debug)]
+pub struct PanicOnClone<'a> {
+    pub value: i32,
+    pub armed: bool,
+    drops: &'a Cell<usize>,
+}
+
+impl<'a> PanicOnClone<'a> {
+    pub fn new(drops: &'a Cell<usize>, value: i32) -> Self {
+        PanicOnClone {
+            value,
+            armed: false,
+            drops,
+        }
+    }
+
+    pub fn armed(drops: &'a Cell<usize>, value: i32) -> Self {
+        PanicOnClone {
+            value,
+            armed: true,
+            drops,
+        }
+    }
+}
+
+impl Clone for PanicOnClone<'_> {
+    fn clone(&self) -> Self {
+        if self.armed {
+            panic::panic_any(InjectedPanic);
+        }
+        PanicOnClone::new(self.drops, self.value)
+    }
+}
+
+impl Drop for PanicOnClone<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+// xorshift64*, plenty random for picking operations and no dependencies. The
+// same seed always gives the same numbers, so a failing seed reproduces.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
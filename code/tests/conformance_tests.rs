@@ -0,0 +1,248 @@
+// The same scenarios run against every list through the shared traits. The
+// scenarios only know about the trait, so they double as a check that a list
+// can be swapped for another one by changing a type parameter.
+use code::traits::{Deque, Queue, Stack};
+use code::{fifth, first, fourth, second, sixth, third};
+
+// Deref the peeked value so lists lending a Ref compare like the ones lending &T
+fn peek<S: Stack<i32>>(stack: &S) -> Option<i32> {
+    stack.peek().map(|x| *x)
+}
+
+fn peek_front<Q: Queue<i32>>(queue: &Q) -> Option<i32> {
+    queue.peek_front().map(|x| *x)
+}
+
+fn peek_back<D: Deque<i32>>(deque: &D) -> Option<i32> {
+    deque.peek_back().map(|x| *x)
+}
+
+fn stack_conformance<S: Stack<i32>>(new: fn() -> S) {
+    let mut stack = new();
+    assert_eq!(stack.pop(), None);
+    assert_eq!(peek(&stack), None);
+
+    stack.push(1);
+    stack.push(2);
+    stack.push(3);
+    assert_eq!(peek(&stack), Some(3));
+    assert_eq!(stack.pop(), Some(3));
+    assert_eq!(stack.pop(), Some(2));
+
+    // Pushing after popping doesn't disturb what is left underneath
+    stack.push(4);
+    assert_eq!(peek(&stack), Some(4));
+    assert_eq!(stack.pop(), Some(4));
+    assert_eq!(stack.pop(), Some(1));
+    assert_eq!(stack.pop(), None);
+    assert_eq!(peek(&stack), None);
+
+    // And an emptied stack is as good as a new one
+    stack.push(5);
+    assert_eq!(stack.pop(), Some(5));
+    assert_eq!(stack.pop(), None);
+}
+
+// Long enough that a recursive drop would blow the stack
+fn stack_long<S: Stack<i32>>(new: fn() -> S) {
+    let mut stack = new();
+    for i in 0..100_000 {
+        stack.push(i);
+    }
+    for i in (50_000..100_000).rev() {
+        assert_eq!(stack.pop(), Some(i));
+    }
+    assert_eq!(peek(&stack), Some(49_999));
+}
+
+fn queue_conformance<Q: Queue<i32>>(new: fn() -> Q) {
+    let mut queue = new();
+    assert_eq!(queue.pop_front(), None);
+    assert_eq!(peek_front(&queue), None);
+
+    queue.push_back(1);
+    queue.push_back(2);
+    queue.push_back(3);
+    assert_eq!(peek_front(&queue), Some(1));
+    assert_eq!(queue.pop_front(), Some(1));
+    assert_eq!(queue.pop_front(), Some(2));
+
+    queue.push_back(4);
+    assert_eq!(peek_front(&queue), Some(3));
+    assert_eq!(queue.pop_front(), Some(3));
+    assert_eq!(queue.pop_front(), Some(4));
+    assert_eq!(queue.pop_front(), None);
+    assert_eq!(peek_front(&queue), None);
+
+    // Emptying the queue mustn't leave a stale back end behind
+    queue.push_back(5);
+    queue.push_back(6);
+    assert_eq!(queue.pop_front(), Some(5));
+    assert_eq!(queue.pop_front(), Some(6));
+    assert_eq!(queue.pop_front(), None);
+}
+
+fn queue_long<Q: Queue<i32>>(new: fn() -> Q) {
+    let mut queue = new();
+    for i in 0..100_000 {
+        queue.push_back(i);
+    }
+    for i in 0..50_000 {
+        assert_eq!(queue.pop_front(), Some(i));
+    }
+    assert_eq!(peek_front(&queue), Some(50_000));
+}
+
+fn deque_conformance<D: Deque<i32>>(new: fn() -> D) {
+    queue_conformance(new);
+
+    let mut deque = new();
+    assert_eq!(deque.pop_back(), None);
+    assert_eq!(peek_back(&deque), None);
+
+    deque.push_front(2);
+    deque.push_back(3);
+    deque.push_front(1);
+    // [1, 2, 3]
+    assert_eq!(peek_front(&deque), Some(1));
+    assert_eq!(peek_back(&deque), Some(3));
+    assert_eq!(deque.pop_back(), Some(3));
+    assert_eq!(deque.pop_back(), Some(2));
+
+    // The last element is both the front and the back
+    assert_eq!(peek_front(&deque), Some(1));
+    assert_eq!(peek_back(&deque), Some(1));
+    assert_eq!(deque.pop_back(), Some(1));
+    assert_eq!(deque.pop_back(), None);
+    assert_eq!(deque.pop_front(), None);
+
+    // Used from the back only it's a stack...
+    for i in 0..10 {
+        deque.push_back(i);
+    }
+    for i in (0..10).rev() {
+        assert_eq!(deque.pop_back(), Some(i));
+    }
+
+    // ...and fed from the front and drained from the back it's a queue
+    for i in 0..10 {
+        deque.push_front(i);
+    }
+    for i in 0..10 {
+        assert_eq!(deque.pop_back(), Some(i));
+    }
+    assert_eq!(peek_back(&deque), None);
+}
+
+#[test]
+fn first_is_a_stack() {
+    stack_conformance(first::List::new);
+    stack_long(first::List::new);
+}
+
+#[test]
+fn second_is_a_stack() {
+    stack_conformance(second::List::new);
+    stack_long(second::List::new);
+}
+
+#[test]
+fn third_is_a_stack() {
+    stack_conformance(third::List::new);
+    stack_long(third::List::new);
+}
+
+#[test]
+fn third_pop_leaves_shared_lists_alone() {
+    let mut list = third::List::new().prepend(1).prepend(2);
+    let shared = list.tail();
+
+    assert_eq!(Stack::pop(&mut list), Some(2));
+    assert_eq!(Stack::pop(&mut list), Some(1));
+    assert_eq!(Stack::pop(&mut list), None);
+    assert_eq!(shared.iter().collect::<Vec<_>>(), [&1]);
+}
+
+#[test]
+fn fourth_is_a_stack_and_a_deque() {
+    stack_conformance(fourth::List::new);
+    stack_long(fourth::List::new);
+    deque_conformance(fourth::List::new);
+    queue_long(fourth::List::new);
+}
+
+#[test]
+fn fifth_is_a_queue() {
+    queue_conformance(fifth::raw_pointers::List::new);
+    queue_long(fifth::raw_pointers::List::new);
+}
+
+#[test]
+fn sixth_is_a_stack_and_a_deque() {
+    stack_conformance(sixth::LinkedList::new);
+    stack_long(sixth::LinkedList::new);
+    deque_conformance(sixth::LinkedList::new);
+    queue_long(sixth::LinkedList::new);
+}
+
+// The book's own lists in the lists crate implement the same traits
+#[test]
+fn lists_first_is_a_stack() {
+    stack_conformance(lists::first::List::new);
+    stack_long(lists::first::List::new);
+}
+
+#[test]
+fn lists_second_is_a_stack() {
+    stack_conformance(lists::second::List::new);
+    stack_long(lists::second::List::new);
+}
+
+#[test]
+fn lists_third_is_a_stack() {
+    stack_conformance(lists::third::List::new);
+    stack_long(lists::third::List::new);
+}
+
+#[test]
+fn lists_fourth_is_a_stack_and_a_deque() {
+    stack_conformance(lists::fourth::List::new);
+    stack_long(lists::fourth::List::new);
+    deque_conformance(lists::fourth::List::new);
+    queue_long(lists::fourth::List::new);
+}
+
+#[test]
+fn lists_fifth_is_a_queue() {
+    queue_conformance(lists::fifth::List::new);
+    queue_long(lists::fifth::List::new);
+}
+
+#[test]
+fn lists_silly1_is_a_stack() {
+    stack_conformance(lists::silly1::Stack::new);
+    stack_long(lists::silly1::Stack::new);
+    stack_conformance(lists::silly1::List::new);
+    stack_long(lists::silly1::List::new);
+}
+
+// Something written once against the trait, used with different lists
+fn reverse<S: Stack<i32>>(items: &[i32], mut stack: S) -> Vec<i32> {
+    for &item in items {
+        stack.push(item);
+    }
+    std::iter::from_fn(|| stack.pop()).collect()
+}
+
+#[test]
+fn generic_code_switches_lists() {
+    let items = [1, 2, 3, 4];
+    let expected = vec![4, 3, 2, 1];
+    assert_eq!(reverse(&items, first::List::new()), expected);
+    assert_eq!(reverse(&items, second::List::new()), expected);
+    assert_eq!(reverse(&items, third::List::new()), expected);
+    assert_eq!(reverse(&items, fourth::List::new()), expected);
+    assert_eq!(reverse(&items, sixth::LinkedList::new()), expected);
+    assert_eq!(reverse(&items, lists::second::List::new()), expected);
+    assert_eq!(reverse(&items, lists::silly1::List::new()), expected);
+}
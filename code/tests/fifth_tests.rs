@@ -163,6 +163,8 @@ fn test_slice_1() {
 fn opaque_read(val: &i32) {
     println!("{}", val);
 }
+// The book's stacked borrows experiment, the reborrows are the point of it.
+#[allow(unused_unsafe, clippy::borrow_deref_ref)]
 #[test]
 fn test_shared_read() {
     unsafe {
@@ -6,27 +6,39 @@ fn test_basics_fourth() {
 
     // Check empty list behaves right
     assert_eq!(list.pop_front(), None);
+    list.check_invariants();
 
     // Populate list
     list.push_front(1);
+    list.check_invariants();
     list.push_front(2);
+    list.check_invariants();
     list.push_front(3);
+    list.check_invariants();
 
     // Check normal removal
     assert_eq!(list.pop_front(), Some(3));
+    list.check_invariants();
     assert_eq!(list.pop_front(), Some(2));
+    list.check_invariants();
 
     // Push some more just to make sure nothing's corrupted
     list.push_front(4);
+    list.check_invariants();
     list.push_front(5);
+    list.check_invariants();
 
     // Check normal removal
     assert_eq!(list.pop_front(), Some(5));
+    list.check_invariants();
     assert_eq!(list.pop_front(), Some(4));
+    list.check_invariants();
 
     // Check exhaustion
     assert_eq!(list.pop_front(), Some(1));
+    list.check_invariants();
     assert_eq!(list.pop_front(), None);
+    list.check_invariants();
 }
 
 #[test]
@@ -34,8 +46,11 @@ fn test_peek() {
     let mut list = List::new();
     assert!(list.peek_front().is_none());
     list.push_front(1);
+    list.check_invariants();
     list.push_front(2);
+    list.check_invariants();
     list.push_front(3);
+    list.check_invariants();
 
     // list is `Ref<'_, {integer}>` so we unreference with * and worrow again
     assert_eq!(*list.peek_front().unwrap(), 3);
@@ -47,53 +62,77 @@ fn test_basics2() {
 
     // Check empty list behaves right
     assert_eq!(list.pop_front(), None);
+    list.check_invariants();
 
     // Populate list
     list.push_front(1);
+    list.check_invariants();
     list.push_front(2);
+    list.check_invariants();
     list.push_front(3);
+    list.check_invariants();
 
     // Check normal removal
     assert_eq!(list.pop_front(), Some(3));
+    list.check_invariants();
     assert_eq!(list.pop_front(), Some(2));
+    list.check_invariants();
 
     // Push some more just to make sure nothing's corrupted
     list.push_front(4);
+    list.check_invariants();
     list.push_front(5);
+    list.check_invariants();
 
     // Check normal removal
     assert_eq!(list.pop_front(), Some(5));
+    list.check_invariants();
     assert_eq!(list.pop_front(), Some(4));
+    list.check_invariants();
 
     // Check exhaustion
     assert_eq!(list.pop_front(), Some(1));
+    list.check_invariants();
     assert_eq!(list.pop_front(), None);
+    list.check_invariants();
 
     // ---- back -----
 
     // Check empty list behaves right
     assert_eq!(list.pop_back(), None);
+    list.check_invariants();
 
     // Populate list
     list.push_back(1);
+    list.check_invariants();
     list.push_back(2);
+    list.check_invariants();
     list.push_back(3);
+    list.check_invariants();
 
     // Check normal removal
     assert_eq!(list.pop_back(), Some(3));
+    list.check_invariants();
     assert_eq!(list.pop_back(), Some(2));
+    list.check_invariants();
 
     // Push some more just to make sure nothing's corrupted
     list.push_back(4);
+    list.check_invariants();
     list.push_back(5);
+    list.check_invariants();
 
     // Check normal removal
     assert_eq!(list.pop_back(), Some(5));
+    list.check_invariants();
     assert_eq!(list.pop_back(), Some(4));
+    list.check_invariants();
 
     // Check exhaustion
     assert_eq!(list.pop_back(), Some(1));
+    list.check_invariants();
     assert_eq!(list.pop_back(), None);
+    list.check_invariants();
 }
 
 #[test]
@@ -105,8 +144,11 @@ fn test_peek2() {
     assert!(list.peek_back_mut().is_none());
 
     list.push_front(1);
+    list.check_invariants();
     list.push_front(2);
+    list.check_invariants();
     list.push_front(3);
+    list.check_invariants();
 
     assert_eq!(&*list.peek_front().unwrap(), &3);
     assert_eq!(&mut *list.peek_front_mut().unwrap(), &mut 3);
@@ -118,8 +160,11 @@ fn test_peek2() {
 fn test_into_iter() {
     let mut list = List::new();
     list.push_front(1);
+    list.check_invariants();
     list.push_front(2);
+    list.check_invariants();
     list.push_front(3);
+    list.check_invariants();
 
     let mut iter = list.into_iter();
     assert_eq!(iter.next(), Some(3));
@@ -0,0 +1,405 @@
+// Every list has to give back every node it allocated and drop every element
+// exactly once, however it's torn down: dropped whole, popped, or drained
+// part way by into_iter.
+mod common;
+
+use code::{fifth, first, fourth, second, sixth, third};
+use common::{assert_leak_free, CountingAlloc, DropCounter};
+use std::cell::Cell;
+use std::mem;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const N: i32 = 100;
+
+#[test]
+fn counting_alloc_sees_leaks() {
+    let leaked = std::panic::catch_unwind(|| {
+        assert_leak_free("a forgotten Box", || mem::forget(Box::new(0)));
+    });
+    assert!(leaked.is_err());
+}
+
+#[test]
+fn first_releases_everything() {
+    assert_leak_free("first::List drop", || {
+        let mut list = first::List::new();
+        for i in 0..N {
+            list.push(i);
+        }
+    });
+
+    assert_leak_free("first::List pop", || {
+        let mut list = first::List::new();
+        for i in 0..N {
+            list.push(i);
+        }
+        for _ in 0..N / 2 {
+            list.pop();
+        }
+    });
+}
+
+#[test]
+fn second_releases_everything() {
+    let drops = Cell::new(0);
+    assert_leak_free("second::List drop", || {
+        let mut list = second::List::new();
+        for i in 0..N {
+            list.push(DropCounter::new(&drops, i));
+        }
+        drop(list.pop());
+        if let Some(elem) = list.peek_mut() {
+            elem.value = -1;
+        }
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("second::List into_iter", || {
+        let mut list = second::List::new();
+        for i in 0..N {
+            list.push(DropCounter::new(&drops, i));
+        }
+        let mut iter = list.into_iter();
+        for _ in 0..N / 2 {
+            iter.next();
+        }
+    });
+    assert_eq!(drops.get(), N as usize);
+}
+
+#[test]
+fn second_bulk_operations_release_everything() {
+    let drops = Cell::new(0);
+    assert_leak_free("second::List reverse, append and split_off", || {
+        let mut list = second::List::new();
+        let mut other = second::List::new();
+        for i in 0..N {
+            list.push(DropCounter::new(&drops, i));
+            other.push(DropCounter::new(&drops, i));
+        }
+        list.reverse();
+        list.append(&mut other);
+        let tail = list.split_off(N as usize / 2);
+        drop(list);
+        drop(other);
+        drop(tail);
+    });
+    assert_eq!(drops.get(), 2 * N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("second::List cursor split and splice", || {
+        let mut list = second::List::new();
+        for i in 0..N {
+            list.push(DropCounter::new(&drops, i));
+        }
+        let mut cursor = list.cursor_mut();
+        for _ in 0..10 {
+            cursor.move_next();
+        }
+        let after = cursor.split_after();
+        cursor.splice_after(after);
+        cursor.insert_after(DropCounter::new(&drops, -1));
+        cursor.remove_next();
+        cursor.remove_next();
+    });
+    assert_eq!(drops.get(), N as usize + 1);
+}
+
+#[test]
+fn third_releases_everything() {
+    let drops = Cell::new(0);
+    assert_leak_free("third::List drop", || {
+        let mut list = third::List::new();
+        for i in 0..N {
+            list = list.prepend(DropCounter::new(&drops, i));
+        }
+    });
+    assert_eq!(drops.get(), N as usize);
+}
+
+fn shared_lists(drops: &Cell<usize>) -> [third::List<DropCounter<'_>>; 4] {
+    let base = (0..N).fold(third::List::new(), |list, i| {
+        list.prepend(DropCounter::new(drops, i))
+    });
+    let longer = base.prepend(DropCounter::new(drops, N));
+    let shorter = base.tail().tail();
+    let branch = shorter.prepend(DropCounter::new(drops, N + 1));
+    [base, longer, shorter, branch]
+}
+
+// Drop stops as soon as it reaches a node another list still points at, so
+// whichever list goes last has to free what the others left behind.
+#[test]
+fn third_shared_tails_release_everything() {
+    // Every order the four lists could be dropped in
+    let orders = (0..256)
+        .map(|k| [k % 4, k / 4 % 4, k / 16 % 4, k / 64])
+        .filter(|order| (0..4).all(|i| order.contains(&i)));
+
+    for order in orders {
+        let drops = Cell::new(0);
+        assert_leak_free("third::List with shared tails", || {
+            let mut lists = shared_lists(&drops).map(Some);
+            for i in order {
+                lists[i] = None;
+            }
+        });
+        assert_eq!(drops.get(), N as usize + 2);
+    }
+}
+
+#[test]
+fn third_stack_pop_releases_shared_nodes() {
+    use code::traits::Stack;
+
+    let drops = Cell::new(0);
+    assert_leak_free("third::List popped while shared", || {
+        let mut list = third::List::new();
+        for i in 0..N {
+            list = list.prepend(DropCounter::new(&drops, i));
+        }
+        let shared = list.tail();
+        // The head is ours alone and gets moved out, the rest is shared
+        // with `shared` and has to be cloned.
+        while list.pop().is_some() {}
+        drop(shared);
+    });
+    // Every element once, plus the N - 1 clones pop had to make
+    assert_eq!(drops.get(), 2 * N as usize - 1);
+}
+
+#[test]
+fn fourth_releases_everything() {
+    let drops = Cell::new(0);
+    assert_leak_free("fourth::List drop", || {
+        let mut list = fourth::List::new();
+        for i in 0..N {
+            list.push_front(DropCounter::new(&drops, i));
+            list.push_back(DropCounter::new(&drops, i));
+        }
+        // Borrowing an element doesn't keep its node alive past the list
+        let front = list.peek_front().map(|elem| elem.value);
+        let back = list.peek_back().map(|elem| elem.value);
+        assert_eq!((front, back), (Some(N - 1), Some(N - 1)));
+    });
+    assert_eq!(drops.get(), 2 * N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("fourth::List pop", || {
+        let mut list = fourth::List::new();
+        for i in 0..N {
+            list.push_back(DropCounter::new(&drops, i));
+        }
+        for _ in 0..N / 4 {
+            list.pop_front();
+            list.pop_back();
+        }
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("fourth::List into_iter", || {
+        let mut list = fourth::List::new();
+        for i in 0..N {
+            list.push_back(DropCounter::new(&drops, i));
+        }
+        let mut iter = list.into_iter();
+        iter.next();
+        iter.next_back();
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    // Down to one node, where head and tail both hold it
+    let drops = Cell::new(0);
+    assert_leak_free("fourth::List single node", || {
+        let mut list = fourth::List::new();
+        list.push_back(DropCounter::new(&drops, 0));
+        list.push_back(DropCounter::new(&drops, 1));
+        list.pop_back();
+    });
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn fifth_releases_everything() {
+    let drops = Cell::new(0);
+    assert_leak_free("fifth::raw_pointers::List drop", || {
+        let mut list = fifth::raw_pointers::List::new();
+        for i in 0..N {
+            list.push(DropCounter::new(&drops, i));
+        }
+        for _ in 0..N / 2 {
+            list.pop();
+        }
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("fifth::raw_pointers::List into_iter", || {
+        let mut list = fifth::raw_pointers::List::new();
+        for i in 0..N {
+            list.push(DropCounter::new(&drops, i));
+        }
+        let mut iter = list.into_iter();
+        iter.next();
+    });
+    assert_eq!(drops.get(), N as usize);
+}
+
+fn sixth_list(drops: &Cell<usize>) -> sixth::LinkedList<DropCounter<'_>> {
+    (0..N).map(|i| DropCounter::new(drops, i)).collect()
+}
+
+#[test]
+fn sixth_releases_everything() {
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList drop", || {
+        let mut list = sixth_list(&drops);
+        list.pop_front();
+        list.pop_back();
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList into_iter", || {
+        let mut iter = sixth_list(&drops).into_iter();
+        iter.next();
+        iter.next_back();
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList clear", || {
+        let mut list = sixth_list(&drops);
+        list.clear();
+        assert_eq!(drops.get(), N as usize);
+        list.push_back(DropCounter::new(&drops, 0));
+    });
+    assert_eq!(drops.get(), N as usize + 1);
+}
+
+// Iterators and cursors left half way must leave the list whole. ExtractIf
+// unlinks lazily, so whatever it hasn't reached stays in the list.
+#[test]
+fn sixth_survives_abandoned_borrows() {
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList with abandoned borrows", || {
+        let mut list = sixth_list(&drops);
+        {
+            let mut iter = list.iter_mut();
+            iter.next();
+            iter.next_back();
+        }
+        {
+            let mut cursor = list.cursor_mut();
+            cursor.move_next();
+            cursor.remove_current();
+        }
+        {
+            let mut extract = list.extract_if(|elem| elem.value % 2 == 0);
+            extract.next();
+            extract.next();
+        }
+        list.check_invariants();
+        assert_eq!(list.len(), N as usize - 3);
+    });
+    assert_eq!(drops.get(), N as usize);
+}
+
+// The borrowing iterators have nothing to forget. Forgetting an IntoIter
+// leaks the nodes it still owns, and only those: what it already handed out
+// is gone, and nothing is dropped twice.
+#[test]
+fn sixth_forgotten_into_iter_leaks_only_the_rest() {
+    let drops = Cell::new(0);
+    let before = common::live_allocations();
+    let mut iter = sixth_list(&drops).into_iter();
+    iter.next();
+    iter.next_back();
+    mem::forget(iter);
+    assert_eq!(common::live_allocations() - before, N as isize - 2);
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn sixth_bulk_operations_release_everything() {
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList append and split_off", || {
+        let mut list = sixth_list(&drops);
+        let mut other = sixth_list(&drops);
+        list.append(&mut other);
+        let tail = list.split_off(N as usize / 2);
+        drop(list);
+        drop(other);
+        drop(tail);
+    });
+    assert_eq!(drops.get(), 2 * N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList cursor split and splice", || {
+        let mut list = sixth_list(&drops);
+        let mut cursor = list.cursor_mut();
+        for _ in 0..10 {
+            cursor.move_next();
+        }
+        let before = cursor.split_before();
+        let after = cursor.split_after();
+        cursor.splice_after(before);
+        cursor.splice_before(after);
+        cursor.insert_after(DropCounter::new(&drops, -1));
+        cursor.remove_current();
+    });
+    assert_eq!(drops.get(), N as usize + 1);
+
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList sort, retain and dedup", || {
+        let mut list = sixth_list(&drops);
+        list.extend((0..N).map(|i| DropCounter::new(&drops, i)));
+        list.sort_by_key(|elem| elem.value);
+        list.dedup();
+        list.retain(|elem| elem.value % 3 != 0);
+        let extracted: Vec<_> = list.extract_if(|elem| elem.value % 2 == 0).collect();
+        assert!(!extracted.is_empty());
+        list.insert(1, DropCounter::new(&drops, -1));
+        list.remove(0);
+    });
+    assert_eq!(drops.get(), 2 * N as usize + 1);
+
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList clone", || {
+        let list = sixth_list(&drops);
+        assert_eq!(list.clone(), list);
+    });
+    assert_eq!(drops.get(), 2 * N as usize);
+}
+
+// Pooled nodes are still allocations, they have to go when the list does.
+#[test]
+fn sixth_recycling_pool_is_released() {
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList recycling", || {
+        let mut list = sixth::LinkedList::new();
+        list.set_recycling(true);
+        list.reserve(N as usize);
+        for i in 0..N {
+            list.push_back(DropCounter::new(&drops, i));
+        }
+        for _ in 0..N / 2 {
+            list.pop_front();
+        }
+        assert!(list.capacity() >= N as usize / 2);
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    assert_leak_free("sixth::LinkedList shrink_to_fit", || {
+        let mut list = sixth::LinkedList::<i32>::new();
+        list.set_recycling(true);
+        list.reserve(N as usize);
+        list.shrink_to_fit();
+        assert_eq!(list.capacity(), 0);
+        mem::forget(list);
+    });
+}
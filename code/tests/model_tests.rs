@@ -0,0 +1,382 @@
+// Randomized model checking: every list gets fed the same random operations
+// as a std collection and has to give the same answers. When they disagree the
+// operation sequence is shrunk down to a minimal reproducer before reporting.
+mod common;
+
+use code::{fifth, first, fourth, second, sixth};
+use common::Rng;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    PushFront(i32),
+    PushBack(i32),
+    PopFront,
+    PopBack,
+    PeekFront,
+    PeekBack,
+    Iter,
+}
+
+// What an operation returned, so the list and the model can be compared.
+#[derive(Debug, PartialEq)]
+enum Out {
+    Unit,
+    Elem(Option<i32>),
+    All(Vec<i32>),
+}
+
+// Anything that can run operations: the lists under test and the models.
+// Each implementation only has to handle the ops it lists in OPS.
+trait Subject {
+    const OPS: &'static [Op];
+
+    fn empty() -> Self;
+    fn apply(&mut self, op: Op) -> Out;
+}
+
+// A stack's top is the "front", so stacks only use the front ops.
+const STACK_OPS: &[Op] = &[Op::PushFront(0), Op::PopFront];
+const PEEK_STACK_OPS: &[Op] = &[Op::PushFront(0), Op::PopFront, Op::PeekFront, Op::Iter];
+const QUEUE_OPS: &[Op] = &[Op::PushBack(0), Op::PopFront, Op::PeekFront, Op::Iter];
+const DEQUE_OPS: &[Op] = &[
+    Op::PushFront(0),
+    Op::PushBack(0),
+    Op::PopFront,
+    Op::PopBack,
+    Op::PeekFront,
+    Op::PeekBack,
+];
+const ALL_OPS: &[Op] = &[
+    Op::PushFront(0),
+    Op::PushBack(0),
+    Op::PopFront,
+    Op::PopBack,
+    Op::PeekFront,
+    Op::PeekBack,
+    Op::Iter,
+];
+
+// Reference for the stacks, the top of the stack is the end of the Vec.
+struct StackModel(Vec<i32>);
+
+impl Subject for StackModel {
+    const OPS: &'static [Op] = PEEK_STACK_OPS;
+
+    fn empty() -> Self {
+        StackModel(Vec::new())
+    }
+
+    fn apply(&mut self, op: Op) -> Out {
+        match op {
+            Op::PushFront(x) => {
+                self.0.push(x);
+                Out::Unit
+            }
+            Op::PopFront => Out::Elem(self.0.pop()),
+            Op::PeekFront => Out::Elem(self.0.last().copied()),
+            Op::Iter => Out::All(self.0.iter().rev().copied().collect()),
+            _ => unreachable!("{:?} is not a stack operation", op),
+        }
+    }
+}
+
+// Reference for the queues and deques.
+struct DequeModel(VecDeque<i32>);
+
+impl Subject for DequeModel {
+    const OPS: &'static [Op] = ALL_OPS;
+
+    fn empty() -> Self {
+        DequeModel(VecDeque::new())
+    }
+
+    fn apply(&mut self, op: Op) -> Out {
+        match op {
+            Op::PushFront(x) => {
+                self.0.push_front(x);
+                Out::Unit
+            }
+            Op::PushBack(x) => {
+                self.0.push_back(x);
+                Out::Unit
+            }
+            Op::PopFront => Out::Elem(self.0.pop_front()),
+            Op::PopBack => Out::Elem(self.0.pop_back()),
+            Op::PeekFront => Out::Elem(self.0.front().copied()),
+            Op::PeekBack => Out::Elem(self.0.back().copied()),
+            Op::Iter => Out::All(self.0.iter().copied().collect()),
+        }
+    }
+}
+
+impl Subject for first::List {
+    const OPS: &'static [Op] = STACK_OPS;
+
+    fn empty() -> Self {
+        first::List::new()
+    }
+
+    fn apply(&mut self, op: Op) -> Out {
+        match op {
+            Op::PushFront(x) => {
+                self.push(x);
+                Out::Unit
+            }
+            Op::PopFront => Out::Elem(self.pop()),
+            _ => unreachable!("{:?} is not supported by first::List", op),
+        }
+    }
+}
+
+impl Subject for second::List<i32> {
+    const OPS: &'static [Op] = PEEK_STACK_OPS;
+
+    fn empty() -> Self {
+        second::List::new()
+    }
+
+    fn apply(&mut self, op: Op) -> Out {
+        let out = match op {
+            Op::PushFront(x) => {
+                self.push(x);
+                Out::Unit
+            }
+            Op::PopFront => Out::Elem(self.pop()),
+            Op::PeekFront => Out::Elem(self.peek().copied()),
+            Op::Iter => Out::All(self.iter().copied().collect()),
+            _ => unreachable!("{:?} is not supported by second::List", op),
+        };
+        // The stored len has to match what's really there
+        assert_eq!(self.len(), self.iter().count());
+        out
+    }
+}
+
+impl Subject for fifth::raw_pointers::List<i32> {
+    const OPS: &'static [Op] = QUEUE_OPS;
+
+    fn empty() -> Self {
+        fifth::raw_pointers::List::new()
+    }
+
+    fn apply(&mut self, op: Op) -> Out {
+        let out = match op {
+            Op::PushBack(x) => {
+                self.push(x);
+                Out::Unit
+            }
+            Op::PopFront => Out::Elem(self.pop()),
+            Op::PeekFront => Out::Elem(self.peek().copied()),
+            Op::Iter => Out::All(self.iter().copied().collect()),
+            _ => unreachable!("{:?} is not supported by fifth::raw_pointers::List", op),
+        };
+        self.check_invariants();
+        out
+    }
+}
+
+impl Subject for fourth::List<i32> {
+    const OPS: &'static [Op] = DEQUE_OPS;
+
+    fn empty() -> Self {
+        fourth::List::new()
+    }
+
+    fn apply(&mut self, op: Op) -> Out {
+        let out = match op {
+            Op::PushFront(x) => {
+                self.push_front(x);
+                Out::Unit
+            }
+            Op::PushBack(x) => {
+                self.push_back(x);
+                Out::Unit
+            }
+            Op::PopFront => Out::Elem(self.pop_front()),
+            Op::PopBack => Out::Elem(self.pop_back()),
+            Op::PeekFront => Out::Elem(self.peek_front().map(|x| *x)),
+            Op::PeekBack => Out::Elem(self.peek_back().map(|x| *x)),
+            Op::Iter => unreachable!("fourth::List has no borrowing iterator"),
+        };
+        self.check_invariants();
+        out
+    }
+}
+
+impl Subject for sixth::LinkedList<i32> {
+    const OPS: &'static [Op] = ALL_OPS;
+
+    fn empty() -> Self {
+        sixth::LinkedList::new()
+    }
+
+    fn apply(&mut self, op: Op) -> Out {
+        let out = match op {
+            Op::PushFront(x) => {
+                self.push_front(x);
+                Out::Unit
+            }
+            Op::PushBack(x) => {
+                self.push_back(x);
+                Out::Unit
+            }
+            Op::PopFront => Out::Elem(self.pop_front()),
+            Op::PopBack => Out::Elem(self.pop_back()),
+            Op::PeekFront => Out::Elem(self.front().copied()),
+            Op::PeekBack => Out::Elem(self.back().copied()),
+            Op::Iter => Out::All(self.iter().copied().collect()),
+        };
+        self.check_invariants();
+        out
+    }
+}
+
+fn generate(rng: &mut Rng, kinds: &[Op], len: usize) -> Vec<Op> {
+    (0..len)
+        .map(|_| match kinds[rng.below(kinds.len())] {
+            Op::PushFront(_) => Op::PushFront(rng.below(100) as i32),
+            Op::PushBack(_) => Op::PushBack(rng.below(100) as i32),
+            op => op,
+        })
+        .collect()
+}
+
+// Runs ops against both and describes the first disagreement, if any.
+// A panic inside the list counts as a disagreement too.
+fn run<S: Subject, M: Subject>(ops: &[Op]) -> Result<(), String> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut subject = S::empty();
+        let mut model = M::empty();
+        for (step, &op) in ops.iter().enumerate() {
+            let got = subject.apply(op);
+            let expected = model.apply(op);
+            if got != expected {
+                return Err(format!(
+                    "step {} ({:?}) returned {:?}, expected {:?}",
+                    step, op, got, expected
+                ));
+            }
+        }
+        Ok(())
+    }));
+    result.unwrap_or_else(|_| Err("the list panicked".to_string()))
+}
+
+// Greedy delta debugging: drop ever smaller chunks of ops while the failure
+// still reproduces, then try to simplify the pushed values to 0.
+fn shrink<S: Subject, M: Subject>(ops: &[Op]) -> Vec<Op> {
+    let mut ops = ops.to_vec();
+    let mut chunk = (ops.len() / 2).max(1);
+    loop {
+        let mut removed = false;
+        let mut i = 0;
+        while i < ops.len() {
+            let mut candidate = ops.clone();
+            candidate.drain(i..(i + chunk).min(ops.len()));
+            if run::<S, M>(&candidate).is_err() {
+                ops = candidate;
+                removed = true;
+            } else {
+                i += chunk;
+            }
+        }
+        if !removed {
+            if chunk == 1 {
+                break;
+            }
+            chunk /= 2;
+        }
+    }
+
+    for i in 0..ops.len() {
+        let simpler = match ops[i] {
+            Op::PushFront(x) if x != 0 => Op::PushFront(0),
+            Op::PushBack(x) if x != 0 => Op::PushBack(0),
+            _ => continue,
+        };
+        let mut candidate = ops.clone();
+        candidate[i] = simpler;
+        if run::<S, M>(&candidate).is_err() {
+            ops = candidate;
+        }
+    }
+    ops
+}
+
+// Returns the shrunk reproducer of the first failing seed, if any.
+fn find_failure<S: Subject, M: Subject>(seeds: u64, len: usize) -> Option<(u64, String, Vec<Op>)> {
+    for seed in 0..seeds {
+        let ops = generate(&mut Rng::new(seed), S::OPS, len);
+        if run::<S, M>(&ops).is_err() {
+            let minimal = shrink::<S, M>(&ops);
+            // Describe the minimal case, not the original one
+            let err = run::<S, M>(&minimal).unwrap_err();
+            return Some((seed, err, minimal));
+        }
+    }
+    None
+}
+
+fn check<S: Subject, M: Subject>(name: &str) {
+    if let Some((seed, err, minimal)) = find_failure::<S, M>(300, 200) {
+        panic!(
+            "{} disagrees with its model (seed {}): {}\nminimal reproducer: {:?}",
+            name, seed, err, minimal
+        );
+    }
+}
+
+#[test]
+fn model_first() {
+    check::<first::List, StackModel>("first::List");
+}
+
+#[test]
+fn model_second() {
+    check::<second::List<i32>, StackModel>("second::List");
+}
+
+#[test]
+fn model_fifth_raw_pointers() {
+    check::<fifth::raw_pointers::List<i32>, DequeModel>("fifth::raw_pointers::List");
+}
+
+#[test]
+fn model_fourth() {
+    check::<fourth::List<i32>, DequeModel>("fourth::List");
+}
+
+#[test]
+fn model_sixth() {
+    check::<sixth::LinkedList<i32>, DequeModel>("sixth::LinkedList");
+}
+
+// A deliberately broken deque, to prove the harness catches and shrinks bugs.
+struct Forgetful(sixth::LinkedList<i32>);
+
+impl Subject for Forgetful {
+    const OPS: &'static [Op] = ALL_OPS;
+
+    fn empty() -> Self {
+        Forgetful(sixth::LinkedList::new())
+    }
+
+    fn apply(&mut self, op: Op) -> Out {
+        match op {
+            // Silently drops pushes to the back once there are 3 elements
+            Op::PushBack(_) if self.0.len() >= 3 => Out::Unit,
+            _ => self.0.apply(op),
+        }
+    }
+}
+
+#[test]
+fn model_harness_shrinks() {
+    let (_, err, minimal) = find_failure::<Forgetful, DequeModel>(300, 200)
+        .expect("the harness should notice the lost pushes");
+    // Three elements, the dropped push, and one op to observe it. Which
+    // observing op survives depends on the seed, the length doesn't.
+    assert_eq!(minimal.len(), 5, "{}: {:?}", err, minimal);
+}
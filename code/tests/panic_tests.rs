@@ -0,0 +1,345 @@
+// Elements whose Drop or Clone panics, planted in the middle of every list.
+// Whatever the panic interrupts, the list must still free every node and drop
+// every element exactly once: no leaks and no double frees.
+//
+// first::List only holds i32s, so there is nothing there to inject a panic into.
+mod common;
+
+use code::{fifth, fourth, second, sixth, third};
+use common::{
+    assert_leak_free, expect_injected_panic, quiet_injected_panics, CountingAlloc, DropCounter,
+    InjectedPanic, PanicOnClone, PanicOnDrop,
+};
+use std::cell::Cell;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const N: i32 = 20;
+// Where the armed element goes, so there are nodes on both sides of it
+const ARMED: i32 = N / 2;
+
+fn elem(drops: &Cell<usize>, value: i32) -> PanicOnDrop<'_> {
+    if value == ARMED {
+        PanicOnDrop::armed(drops, value)
+    } else {
+        PanicOnDrop::new(drops, value)
+    }
+}
+
+#[test]
+fn second_drop() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("second::List drop", || {
+        expect_injected_panic(|| {
+            let mut list = second::List::new();
+            for i in 0..N {
+                list.push(elem(&drops, i));
+            }
+        })
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("second::List into_iter drop", || {
+        expect_injected_panic(|| {
+            let mut list = second::List::new();
+            for i in 0..N {
+                list.push(elem(&drops, i));
+            }
+            let mut iter = list.into_iter();
+            iter.next();
+        })
+    });
+    assert_eq!(drops.get(), N as usize);
+}
+
+// A compare that panics part way through leaves the list partially sorted but
+// whole, with the nodes taken off for merging put back.
+#[test]
+fn second_sort() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("second::List sort_by", || {
+        let mut list: second::List<_> = (0..N)
+            .map(|i| DropCounter::new(&drops, (i * 7) % N))
+            .collect();
+        let mut compares = 0;
+        expect_injected_panic(|| {
+            list.sort_by(|a, b| {
+                compares += 1;
+                if compares == 3 * N {
+                    std::panic::panic_any(InjectedPanic);
+                }
+                a.value.cmp(&b.value)
+            })
+        });
+        assert_eq!(list.len(), N as usize);
+        let mut values: Vec<i32> = list.iter().map(|elem| elem.value).collect();
+        values.sort();
+        assert!(values.into_iter().eq(0..N));
+        assert_eq!(drops.get(), 0);
+    });
+    assert_eq!(drops.get(), N as usize);
+}
+
+#[test]
+fn third_drop() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("third::List drop", || {
+        expect_injected_panic(|| {
+            (0..N).fold(third::List::new(), |list, i| list.prepend(elem(&drops, i)))
+        })
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    // The panic comes from whichever list ends up dropping the shared nodes
+    let drops = Cell::new(0);
+    assert_leak_free("third::List drop with shared nodes", || {
+        let list = (0..N).fold(third::List::new(), |list, i| list.prepend(elem(&drops, i)));
+        let shared = list.tail().tail();
+        drop(list);
+        assert_eq!(drops.get(), 2);
+        expect_injected_panic(|| drop(shared));
+    });
+    assert_eq!(drops.get(), N as usize);
+}
+
+#[test]
+fn fourth_drop() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("fourth::List drop", || {
+        expect_injected_panic(|| {
+            let mut list = fourth::List::new();
+            for i in 0..N {
+                list.push_back(elem(&drops, i));
+            }
+        })
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("fourth::List into_iter drop", || {
+        expect_injected_panic(|| {
+            let mut list = fourth::List::new();
+            for i in 0..N {
+                list.push_front(elem(&drops, i));
+            }
+            let mut iter = list.into_iter();
+            iter.next();
+            iter.next_back();
+        })
+    });
+    assert_eq!(drops.get(), N as usize);
+}
+
+#[test]
+fn fourth_pop() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("fourth::List pop", || {
+        let mut list = fourth::List::new();
+        list.push_back(elem(&drops, 0));
+        list.push_back(elem(&drops, ARMED));
+        list.push_back(elem(&drops, 1));
+        list.pop_front();
+        // The element only blows up once pop has handed it over
+        expect_injected_panic(|| list.pop_front());
+        list.check_invariants();
+        assert_eq!(list.peek_front().map(|elem| elem.value), Some(1));
+    });
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn fifth_drop() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("fifth::raw_pointers::List drop", || {
+        expect_injected_panic(|| {
+            let mut list = fifth::raw_pointers::List::new();
+            for i in 0..N {
+                list.push(elem(&drops, i));
+            }
+        })
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("fifth::raw_pointers::List into_iter drop", || {
+        expect_injected_panic(|| {
+            let mut list = fifth::raw_pointers::List::new();
+            for i in 0..N {
+                list.push(elem(&drops, i));
+            }
+            let mut iter = list.into_iter();
+            iter.next();
+        })
+    });
+    assert_eq!(drops.get(), N as usize);
+}
+
+fn sixth_list(drops: &Cell<usize>) -> sixth::LinkedList<PanicOnDrop<'_>> {
+    (0..N).map(|i| elem(drops, i)).collect()
+}
+
+#[test]
+fn sixth_drop() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList drop", || {
+        expect_injected_panic(|| sixth_list(&drops))
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList into_iter drop", || {
+        expect_injected_panic(|| {
+            let mut iter = sixth_list(&drops).into_iter();
+            iter.next();
+            iter.next_back();
+        })
+    });
+    assert_eq!(drops.get(), N as usize);
+
+    // The pool has to be emptied even when dropping the elements panicked
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList drop while recycling", || {
+        expect_injected_panic(|| {
+            let mut list = sixth::LinkedList::new();
+            list.set_recycling(true);
+            list.reserve(2 * N as usize);
+            list.extend((0..N).map(|i| elem(&drops, i)));
+        })
+    });
+    assert_eq!(drops.get(), N as usize);
+}
+
+// Every way an element leaves the list: it must be unlinked before its drop
+// gets a chance to panic, so the list is still whole afterwards.
+#[test]
+fn sixth_pop_paths() {
+    quiet_injected_panics();
+    type Remove = fn(&mut sixth::LinkedList<PanicOnDrop<'_>>);
+    let paths: [(&str, Remove); 8] = [
+        ("pop_front", |list| while list.pop_front().is_some() {}),
+        ("pop_back", |list| while list.pop_back().is_some() {}),
+        ("remove", |list| {
+            list.remove(ARMED as usize);
+        }),
+        ("clear", |list| list.clear()),
+        ("retain", |list| list.retain(|elem| elem.value % 2 == 1)),
+        ("dedup_by_key", |list| {
+            list.dedup_by_key(|elem| elem.value / 4)
+        }),
+        ("extract_if", |list| {
+            list.extract_if(|elem| elem.value >= ARMED).for_each(drop);
+        }),
+        ("remove_current", |list| {
+            let mut cursor = list.cursor_mut();
+            cursor.move_next();
+            while cursor.remove_current().is_some() {}
+        }),
+    ];
+
+    for (name, remove) in paths {
+        let drops = Cell::new(0);
+        assert_leak_free(name, || {
+            let mut list = sixth_list(&drops);
+            expect_injected_panic(|| remove(&mut list));
+            list.check_invariants();
+            // Nothing that is still in the list has been dropped
+            assert_eq!(drops.get() + list.len(), N as usize, "{}", name);
+            assert!(list.iter().all(|elem| elem.value != ARMED), "{}", name);
+        });
+        assert_eq!(drops.get(), N as usize, "{}", name);
+    }
+}
+
+fn clone_list(drops: &Cell<usize>) -> sixth::LinkedList<PanicOnClone<'_>> {
+    (0..N)
+        .map(|i| {
+            if i == ARMED {
+                PanicOnClone::armed(drops, i)
+            } else {
+                PanicOnClone::new(drops, i)
+            }
+        })
+        .collect()
+}
+
+fn second_clone_list(drops: &Cell<usize>) -> second::List<PanicOnClone<'_>> {
+    // Pushed from the bottom up, so the list reads 0..N from the top
+    (0..N)
+        .rev()
+        .map(|i| {
+            if i == ARMED {
+                PanicOnClone::armed(drops, i)
+            } else {
+                PanicOnClone::new(drops, i)
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn second_clone() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("second::List clone", || {
+        let list = second_clone_list(&drops);
+        expect_injected_panic(|| list.clone());
+        assert_eq!(drops.get(), ARMED as usize);
+        assert_eq!(list.len(), N as usize);
+    });
+    assert_eq!(drops.get(), (N + ARMED) as usize);
+}
+
+#[test]
+fn second_extend() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("second::List extend", || {
+        let source = second_clone_list(&drops);
+        let mut list = second::List::new();
+        expect_injected_panic(|| list.extend(source.iter().cloned()));
+        assert_eq!(list.len(), ARMED as usize);
+        assert_eq!(list.iter().count(), ARMED as usize);
+        assert_eq!(drops.get(), 0);
+    });
+    assert_eq!(drops.get(), (N + ARMED) as usize);
+}
+
+#[test]
+fn sixth_clone() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList clone", || {
+        let list = clone_list(&drops);
+        // The clones made before the panic are dropped with the half built list
+        expect_injected_panic(|| list.clone());
+        assert_eq!(drops.get(), ARMED as usize);
+        list.check_invariants();
+        assert_eq!(list.len(), N as usize);
+    });
+    assert_eq!(drops.get(), (N + ARMED) as usize);
+}
+
+#[test]
+fn sixth_extend() {
+    quiet_injected_panics();
+    let drops = Cell::new(0);
+    assert_leak_free("sixth::LinkedList extend", || {
+        let source = clone_list(&drops);
+        let mut list = sixth::LinkedList::new();
+        // Whatever made it in before the panic stays in
+        expect_injected_panic(|| list.extend(source.iter().cloned()));
+        list.check_invariants();
+        assert_eq!(list.len(), ARMED as usize);
+        assert_eq!(drops.get(), 0);
+    });
+    assert_eq!(drops.get(), (N + ARMED) as usize);
+}
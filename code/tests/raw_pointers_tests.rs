@@ -6,56 +6,85 @@ fn test_raw_pointers_1() {
 
     // Check empty list behaves right
     assert_eq!(list.pop(), None);
+    list.check_invariants();
 
     // Populate list
     list.push(1);
+    list.check_invariants();
     list.push(2);
+    list.check_invariants();
     list.push(3);
+    list.check_invariants();
 
     // Check normal removal
     assert_eq!(list.pop(), Some(1));
+    list.check_invariants();
     assert_eq!(list.pop(), Some(2));
+    list.check_invariants();
 
     // Push some more just to make sure nothing's corrupted
     list.push(4);
+    list.check_invariants();
     list.push(5);
+    list.check_invariants();
 
     // Check normal removal
     assert_eq!(list.pop(), Some(3));
+    list.check_invariants();
     assert_eq!(list.pop(), Some(4));
+    list.check_invariants();
 
     // Check exhaustion
     assert_eq!(list.pop(), Some(5));
+    list.check_invariants();
     assert_eq!(list.pop(), None);
+    list.check_invariants();
 
     // Check the exhaustion case fixed the pointer right
     list.push(6);
+    list.check_invariants();
     list.push(7);
+    list.check_invariants();
 
     // Check normal removal
     assert_eq!(list.pop(), Some(6));
+    list.check_invariants();
     assert_eq!(list.pop(), Some(7));
+    list.check_invariants();
     assert_eq!(list.pop(), None);
+    list.check_invariants();
 }
 
+// Kept as the book wrote it
+#[allow(clippy::option_map_unit_fn)]
 #[test]
 fn miri_food() {
     let mut list = List::new();
 
     list.push(1);
+    list.check_invariants();
     list.push(2);
+    list.check_invariants();
     list.push(3);
+    list.check_invariants();
 
     assert!(list.pop() == Some(1));
+    list.check_invariants();
     list.push(4);
+    list.check_invariants();
     assert!(list.pop() == Some(2));
+    list.check_invariants();
     list.push(5);
+    list.check_invariants();
 
     assert!(list.peek() == Some(&3));
     list.push(6);
+    list.check_invariants();
     list.peek_mut().map(|x| *x *= 10);
+    list.check_invariants();
     assert!(list.peek() == Some(&30));
     assert!(list.pop() == Some(30));
+    list.check_invariants();
 
     for elem in list.iter_mut() {
         *elem *= 100;
@@ -69,9 +98,12 @@ fn miri_food() {
     assert_eq!(iter.next(), None);
 
     assert!(list.pop() == Some(400));
+    list.check_invariants();
     list.peek_mut().map(|x| *x *= 10);
+    list.check_invariants();
     assert!(list.peek() == Some(&5000));
     list.push(7);
+    list.check_invariants();
 
     // Drop it on the ground and let the dtor exercise itself
 }
@@ -0,0 +1,136 @@
+// Drives the lists-repl binary with a script on stdin, the way a bug
+// reproduction would.
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn repl(list: &str, script: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lists-repl"))
+        .arg(list)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(script.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn repl_stack() {
+    let out = repl("second", "push 1\npush 2\npeek\npop\niter\nfrobnicate\n");
+    assert_eq!(
+        out,
+        "second: [1]\n\
+         second: [2, 1]\n\
+         => 2\n\
+         second: [2, 1]\n\
+         => 2\n\
+         second: [1]\n\
+         [1]\n\
+         error: second has no `frobnicate`, try `help`\n"
+    );
+}
+
+#[test]
+fn repl_lists_without_iterators() {
+    // Looking inside first and fourth takes them apart, it has to put them
+    // back together the same way
+    let out = repl("first", "push 1\npush 2\niter\npop\n");
+    assert!(out.ends_with("[2, 1]\n=> 2\nfirst: [1]\n"), "{}", out);
+
+    let out = repl(
+        "fourth",
+        "push_back 1\npush_back 2\npush_front 0\niter\npop_back\n",
+    );
+    assert!(
+        out.ends_with("[0, 1, 2]\n=> 2\nfourth: [0, 1]\n"),
+        "{}",
+        out
+    );
+}
+
+#[test]
+fn repl_sixth_cursor() {
+    let script = "\
+push_back 1
+push_back 2
+push_back 3
+cursor next
+cursor next
+cursor insert_after 9
+cursor current
+dump
+cursor remove
+dump
+pop_front
+dump
+";
+    let out = repl("sixth", script);
+    let lines: Vec<_> = out.lines().collect();
+    assert_eq!(
+        lines[5..],
+        [
+            "sixth: [1, 2, 9, 3]",
+            "=> 2",
+            "sixth: [1, 2, 9, 3]",
+            "front -> [1] <-> ([2]) <-> [9] <-> [3] <- back",
+            "=> 2",
+            "sixth: [1, 9, 3]",
+            "front -> [1] <-> ([9]) <-> [3] <- back",
+            "=> 1",
+            "sixth: [9, 3]",
+            // Editing the list puts the cursor back on the ghost
+            "front -> [9] <-> [3] <- back (ghost)",
+        ]
+    );
+}
+
+#[test]
+fn repl_switches_lists() {
+    let script = "\
+push_left 1
+push_right 2
+left
+dump
+use fifth
+push_back 1
+diagram on
+push_back 2
+new
+use seventh
+";
+    let out = repl("silly1", script);
+    assert_eq!(
+        out,
+        "silly1: [1]\n\
+         silly1: [1, 2]\n\
+         => true\n\
+         silly1: [1, 2]\n\
+         left: None | right: [1] -> [2]\n\
+         fifth: []\n\
+         fifth: [1]\n\
+         fifth: [1]\n\
+         head -> [1] <- tail\n\
+         fifth: [1, 2]\n\
+         head -> [1] -> [2] <- tail\n\
+         fifth: []\n\
+         head -> None\n\
+         error: use one of: first, second, third, fourth, fifth, sixth, silly1\n"
+    );
+}
+
+#[test]
+fn repl_rejects_unknown_lists() {
+    let status = Command::new(env!("CARGO_BIN_EXE_lists-repl"))
+        .arg("seventh")
+        .stderr(Stdio::null())
+        .status()
+        .unwrap();
+    assert!(!status.success());
+}
@@ -30,6 +30,8 @@ fn basics() {
     assert_eq!(list.pop(), None);
 }
 
+// Kept as the book wrote it
+#[allow(clippy::option_map_unit_fn)]
 #[test]
 fn peek() {
     let mut list = second::List::new();
@@ -88,3 +90,466 @@ fn iter_mut() {
     assert_eq!(iter.next(), Some(&mut 2));
     assert_eq!(iter.next(), Some(&mut 1));
 }
+
+fn list_of(elems: &[i32]) -> second::List<i32> {
+    // push puts every element on top, so go backwards to end up in order
+    let mut list = second::List::new();
+    for &elem in elems.iter().rev() {
+        list.push(elem);
+    }
+    list
+}
+
+fn contents(list: &second::List<i32>) -> Vec<i32> {
+    list.iter().copied().collect()
+}
+
+#[test]
+fn reverse() {
+    let mut list = list_of(&[]);
+    list.reverse();
+    assert_eq!(contents(&list), []);
+
+    let mut list = list_of(&[1]);
+    list.reverse();
+    assert_eq!(contents(&list), [1]);
+
+    let mut list = list_of(&[1, 2, 3, 4]);
+    list.reverse();
+    assert_eq!(contents(&list), [4, 3, 2, 1]);
+    assert_eq!(list.pop(), Some(4));
+    list.push(5);
+    list.reverse();
+    assert_eq!(contents(&list), [1, 2, 3, 5]);
+}
+
+#[test]
+fn append() {
+    let mut list = list_of(&[1, 2]);
+    let mut other = list_of(&[3, 4, 5]);
+    list.append(&mut other);
+    assert_eq!(contents(&list), [1, 2, 3, 4, 5]);
+    assert_eq!(other.pop(), None);
+
+    // Either side can be empty
+    list.append(&mut other);
+    assert_eq!(contents(&list), [1, 2, 3, 4, 5]);
+    other.append(&mut list);
+    assert_eq!(contents(&other), [1, 2, 3, 4, 5]);
+    assert_eq!(list.peek(), None);
+
+    // Both lists still work as stacks afterwards
+    list.push(0);
+    other.push(0);
+    assert_eq!(contents(&list), [0]);
+    assert_eq!(contents(&other), [0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn split_off() {
+    let mut list = list_of(&[1, 2, 3, 4, 5]);
+    let rest = list.split_off(2);
+    assert_eq!(contents(&list), [1, 2]);
+    assert_eq!(contents(&rest), [3, 4, 5]);
+
+    let mut list = list_of(&[1, 2, 3]);
+    let rest = list.split_off(0);
+    assert_eq!(contents(&list), []);
+    assert_eq!(contents(&rest), [1, 2, 3]);
+
+    let mut list = list_of(&[1, 2, 3]);
+    let rest = list.split_off(3);
+    assert_eq!(contents(&list), [1, 2, 3]);
+    assert_eq!(contents(&rest), []);
+
+    // Splitting and appending back gives the original list
+    let mut list = list_of(&[1, 2, 3, 4]);
+    let mut rest = list.split_off(1);
+    list.append(&mut rest);
+    assert_eq!(contents(&list), [1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic(expected = "nonexistent index")]
+fn split_off_past_the_end() {
+    list_of(&[1, 2, 3]).split_off(4);
+}
+
+#[test]
+fn bulk_operations_on_long_lists() {
+    // Long enough that a recursive drop would blow the stack
+    const LONG: i32 = 1_000_000;
+    let mut list = second::List::new();
+    for i in 0..LONG {
+        list.push(i);
+    }
+    list.reverse();
+    assert_eq!(list.peek(), Some(&0));
+    let mut rest = list.split_off(LONG as usize / 2);
+    assert_eq!(rest.peek(), Some(&(LONG / 2)));
+    rest.append(&mut list);
+    assert_eq!(rest.peek(), Some(&(LONG / 2)));
+    assert_eq!(rest.iter().count(), LONG as usize);
+}
+
+#[test]
+fn cursor_walk() {
+    let mut list = list_of(&[1, 2, 3]);
+    let mut cursor = list.cursor_mut();
+    // Starts on the ghost before the head
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&mut 1));
+
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.peek_next(), Some(&mut 2));
+    cursor.move_next();
+    if let Some(elem) = cursor.current() {
+        *elem = 20;
+    }
+    if let Some(elem) = cursor.peek_next() {
+        *elem = 30;
+    }
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 30));
+    assert_eq!(cursor.peek_next(), None);
+
+    // Off the end it stays on the ghost
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), None);
+    assert_eq!(contents(&list), [1, 20, 30]);
+
+    let mut empty = second::List::<i32>::new();
+    let mut cursor = empty.cursor_mut();
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), None);
+}
+
+#[test]
+fn cursor_insert_and_remove() {
+    let mut list = list_of(&[1, 2, 3]);
+    let mut cursor = list.cursor_mut();
+    // On the ghost these work on the head
+    cursor.insert_after(0);
+    assert_eq!(cursor.peek_next(), Some(&mut 0));
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.remove_next(), Some(2));
+    cursor.insert_after(5);
+    assert_eq!(cursor.current(), Some(&mut 1));
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.remove_next(), None);
+    // The ghost at the end adds to the bottom
+    cursor.move_next();
+    cursor.insert_after(4);
+    assert_eq!(contents(&list), [0, 1, 5, 3, 4]);
+
+    let mut cursor = list.cursor_mut();
+    while cursor.remove_next().is_some() {}
+    assert_eq!(list.pop(), None);
+}
+
+#[test]
+fn cursor_split_and_splice() {
+    let mut list = list_of(&[1, 2, 3, 4]);
+    let mut cursor = list.cursor_mut();
+    cursor.move_next();
+    cursor.move_next();
+    let rest = cursor.split_after();
+    assert_eq!(cursor.peek_next(), None);
+    assert_eq!(contents(&rest), [3, 4]);
+    assert_eq!(contents(&list), [1, 2]);
+
+    let mut cursor = list.cursor_mut();
+    cursor.splice_after(rest);
+    cursor.move_next();
+    cursor.splice_after(list_of(&[10, 11]));
+    cursor.splice_after(second::List::new());
+    assert_eq!(cursor.current(), Some(&mut 3));
+    assert_eq!(cursor.peek_next(), Some(&mut 10));
+    cursor.move_next();
+    cursor.move_next();
+    cursor.move_next();
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 2));
+    cursor.splice_after(list_of(&[5]));
+    assert_eq!(contents(&list), [3, 10, 11, 4, 1, 2, 5]);
+
+    let mut cursor = list.cursor_mut();
+    let all = cursor.split_after();
+    assert_eq!(list.peek(), None);
+    assert_eq!(contents(&all), [3, 10, 11, 4, 1, 2, 5]);
+}
+
+#[test]
+fn len() {
+    let mut list = second::List::new();
+    assert_eq!(list.len(), 0);
+    assert!(list.is_empty());
+    list.push(1);
+    list.push(2);
+    list.push(3);
+    assert_eq!(list.len(), 3);
+    assert!(!list.is_empty());
+    list.pop();
+    assert_eq!(list.len(), 2);
+    list.pop();
+    list.pop();
+    list.pop();
+    assert_eq!(list.len(), 0);
+
+    // Bulk operations move the count along with the nodes
+    let mut list = list_of(&[1, 2, 3, 4, 5]);
+    list.reverse();
+    assert_eq!(list.len(), 5);
+    let mut rest = list.split_off(2);
+    assert_eq!((list.len(), rest.len()), (2, 3));
+    rest.append(&mut list);
+    assert_eq!((list.len(), rest.len()), (0, 5));
+    assert!(list.is_empty());
+}
+
+#[test]
+fn cursor_keeps_len() {
+    let mut list = list_of(&[1, 2, 3, 4]);
+    let mut cursor = list.cursor_mut();
+    cursor.insert_after(0);
+    cursor.move_next();
+    cursor.move_next();
+    cursor.remove_next();
+    cursor.splice_after(list_of(&[7, 8, 9]));
+    let rest = cursor.split_after();
+    assert_eq!(list.len(), 2);
+    assert_eq!(rest.len(), 5);
+    assert_eq!(contents(&rest), [7, 8, 9, 3, 4]);
+
+    let mut list = list_of(&[1, 2]);
+    let mut cursor = list.cursor_mut();
+    cursor.move_next();
+    cursor.move_next();
+    cursor.move_next();
+    // From the ghost at the end
+    cursor.insert_after(3);
+    cursor.splice_after(list_of(&[4, 5]));
+    let rest = cursor.split_after();
+    assert_eq!(rest.len(), 3);
+    assert_eq!(contents(&rest), [4, 5, 3]);
+    assert_eq!(list.len(), 2);
+}
+
+#[test]
+fn exact_size_iterators() {
+    let mut list = list_of(&[1, 2, 3]);
+
+    let mut iter = list.iter();
+    assert_eq!(iter.len(), 3);
+    iter.next();
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    iter.next();
+    iter.next();
+    assert_eq!(iter.len(), 0);
+    // Fused: stays done
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+
+    let mut iter = list.iter_mut();
+    assert_eq!(iter.len(), 3);
+    iter.next();
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+
+    let mut iter = list.into_iter();
+    assert_eq!(iter.len(), 3);
+    iter.next();
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    iter.next();
+    iter.next();
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn collect_and_extend() {
+    // Both push, the last element goes on top
+    let list: second::List<i32> = (1..=3).collect();
+    assert_eq!(contents(&list), [3, 2, 1]);
+    assert_eq!(list.len(), 3);
+    let mut extended = second::List::new();
+    extended.extend(1..=3);
+    assert_eq!(extended, list);
+
+    let mut list = list;
+    list.extend(4..=5);
+    assert_eq!(contents(&list), [5, 4, 3, 2, 1]);
+    assert_eq!(list.len(), 5);
+
+    // So collecting a list's iter() turns it upside down
+    let flipped: second::List<i32> = list.iter().copied().collect();
+    assert_eq!(contents(&flipped), [1, 2, 3, 4, 5]);
+
+    let empty: second::List<i32> = std::iter::empty().collect();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn default_and_clone() {
+    let list: second::List<i32> = Default::default();
+    assert!(list.is_empty());
+
+    let list = list_of(&[1, 2, 3]);
+    let mut copy = list.clone();
+    assert_eq!(contents(&copy), [1, 2, 3]);
+    assert_eq!(copy.len(), 3);
+    // The copy has its own nodes
+    *copy.peek_mut().unwrap() = 10;
+    assert_eq!(list.peek(), Some(&1));
+}
+
+#[test]
+fn debug() {
+    assert_eq!(format!("{:?}", second::List::<i32>::new()), "[]");
+    assert_eq!(format!("{:?}", list_of(&[1, 2, 3])), "[1, 2, 3]");
+    assert_eq!(format!("{:#?}", list_of(&[1, 2])), "[\n    1,\n    2,\n]");
+
+    // Nothing recurses per node any more
+    let long: second::List<u8> = std::iter::repeat_n(0, 1_000_000).collect();
+    assert_eq!(format!("{:?}", long).len(), 3 * 1_000_000);
+}
+
+#[test]
+fn eq_and_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(list: &second::List<second::List<i32>>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        list.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    assert_eq!(list_of(&[1, 2, 3]), list_of(&[1, 2, 3]));
+    assert_ne!(list_of(&[1, 2, 3]), list_of(&[3, 2, 1]));
+    assert_ne!(list_of(&[1, 2]), list_of(&[1, 2, 3]));
+    assert_eq!(second::List::<i32>::new(), second::List::new());
+
+    let a: second::List<_> = [list_of(&[1]), list_of(&[2, 3])].into_iter().collect();
+    let b: second::List<_> = [list_of(&[1]), list_of(&[2, 3])].into_iter().collect();
+    let c: second::List<_> = [list_of(&[1, 2]), list_of(&[3])].into_iter().collect();
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+    assert_ne!(a, c);
+    assert_ne!(hash(&a), hash(&c));
+}
+
+#[test]
+fn for_loops() {
+    let mut list = list_of(&[1, 2, 3]);
+    for elem in &mut list {
+        *elem *= 10;
+    }
+    let mut seen = Vec::new();
+    for elem in &list {
+        seen.push(*elem);
+    }
+    assert_eq!(seen, [10, 20, 30]);
+    let owned: Vec<i32> = list.into_iter().collect();
+    assert_eq!(owned, [10, 20, 30]);
+}
+
+#[test]
+fn sort() {
+    let mut list = list_of(&[]);
+    list.sort();
+    assert_eq!(contents(&list), []);
+
+    let mut list = list_of(&[1]);
+    list.sort();
+    assert_eq!(contents(&list), [1]);
+
+    // Lengths around the powers of two the passes work in
+    for len in [2, 3, 4, 5, 7, 8, 9, 31, 32, 33, 100] {
+        let mut elems: Vec<i32> = (0..len).map(|i| (i * 37 + 11) % 23).collect();
+        let mut list = list_of(&elems);
+        list.sort();
+        elems.sort();
+        assert_eq!(contents(&list), elems, "len {}", len);
+        assert_eq!(list.len(), len as usize);
+    }
+
+    let mut list = list_of(&[3, 1, 2]);
+    list.sort_by(|a, b| b.cmp(a));
+    assert_eq!(contents(&list), [3, 2, 1]);
+}
+
+#[test]
+fn sort_is_stable() {
+    // Sort by the key only, the second field records the original order
+    let pairs: Vec<(i32, i32)> = (0..50).map(|i| ((i * 7) % 5, i)).collect();
+    let mut list: second::List<(i32, i32)> = pairs.iter().rev().copied().collect();
+    list.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut expected = pairs.clone();
+    expected.sort_by_key(|pair| pair.0);
+    assert!(list.iter().eq(expected.iter()));
+
+    let mut list: second::List<(i32, i32)> = pairs.iter().rev().copied().collect();
+    list.sort_unstable_by_key(|pair| pair.0);
+    assert!(list
+        .iter()
+        .map(|pair| pair.0)
+        .eq(expected.iter().map(|pair| pair.0)));
+}
+
+#[test]
+fn sort_long_list() {
+    const LONG: i32 = 1_000_000;
+    let mut list: second::List<i32> = (0..LONG).collect();
+    list.sort();
+    assert!(list.iter().copied().eq(0..LONG));
+    assert_eq!(list.len(), LONG as usize);
+}
+
+#[test]
+fn merge_sorted() {
+    let merged = second::List::merge_sorted(list_of(&[1, 3, 5, 7]), list_of(&[2, 3, 6]));
+    assert_eq!(contents(&merged), [1, 2, 3, 3, 5, 6, 7]);
+    assert_eq!(merged.len(), 7);
+
+    let merged = second::List::merge_sorted(list_of(&[]), list_of(&[1, 2]));
+    assert_eq!(contents(&merged), [1, 2]);
+    let merged = second::List::merge_sorted(list_of(&[1, 2]), list_of(&[]));
+    assert_eq!(contents(&merged), [1, 2]);
+    let merged = second::List::merge_sorted(list_of(&[]), list_of(&[]));
+    assert!(merged.is_empty());
+
+    // Ties take a's element first
+    #[derive(Debug)]
+    struct Tagged(i32, &'static str);
+    impl PartialEq for Tagged {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Tagged {}
+    impl PartialOrd for Tagged {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Tagged {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+    let a: second::List<_> = [Tagged(2, "a"), Tagged(1, "a")].into_iter().collect();
+    let b: second::List<_> = [Tagged(2, "b"), Tagged(1, "b")].into_iter().collect();
+    let tags: Vec<_> = second::List::merge_sorted(a, b)
+        .iter()
+        .map(|tagged| tagged.1)
+        .collect();
+    assert_eq!(tags, ["a", "b", "a", "b"]);
+}
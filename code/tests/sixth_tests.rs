@@ -1,4 +1,7 @@
-use code::sixth::LinkedList;
+mod common;
+
+use code::sixth::{Allocator, LinkedList};
+use common::Rng;
 
 #[test]
 fn test_sixth_front() {
@@ -7,35 +10,1330 @@ fn test_sixth_front() {
     // Try to break an empty list
     assert_eq!(list.len(), 0);
     assert_eq!(list.pop_front(), None);
+    list.check_invariants();
     assert_eq!(list.len(), 0);
 
     // Try to break a one item list
     list.push_front(10);
+    list.check_invariants();
     assert_eq!(list.len(), 1);
     assert_eq!(list.pop_front(), Some(10));
+    list.check_invariants();
     assert_eq!(list.len(), 0);
     assert_eq!(list.pop_front(), None);
+    list.check_invariants();
     assert_eq!(list.len(), 0);
 
     // Mess around
     list.push_front(10);
+    list.check_invariants();
     assert_eq!(list.len(), 1);
     list.push_front(20);
+    list.check_invariants();
     assert_eq!(list.len(), 2);
     list.push_front(30);
+    list.check_invariants();
     assert_eq!(list.len(), 3);
     assert_eq!(list.pop_front(), Some(30));
+    list.check_invariants();
     assert_eq!(list.len(), 2);
     list.push_front(40);
+    list.check_invariants();
     assert_eq!(list.len(), 3);
     assert_eq!(list.pop_front(), Some(40));
+    list.check_invariants();
     assert_eq!(list.len(), 2);
     assert_eq!(list.pop_front(), Some(20));
+    list.check_invariants();
     assert_eq!(list.len(), 1);
     assert_eq!(list.pop_front(), Some(10));
+    list.check_invariants();
     assert_eq!(list.len(), 0);
     assert_eq!(list.pop_front(), None);
+    list.check_invariants();
     assert_eq!(list.len(), 0);
     assert_eq!(list.pop_front(), None);
+    list.check_invariants();
+    assert_eq!(list.len(), 0);
+}
+
+#[test]
+fn test_sixth_back() {
+    let mut list = LinkedList::new();
+
+    // Try to break an empty list
+    assert_eq!(list.len(), 0);
+    assert_eq!(list.pop_back(), None);
+    list.check_invariants();
+    assert_eq!(list.len(), 0);
+
+    // Try to break a one item list
+    list.push_back(10);
+    list.check_invariants();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list.pop_back(), Some(10));
+    list.check_invariants();
+    assert_eq!(list.len(), 0);
+    assert_eq!(list.pop_back(), None);
+    list.check_invariants();
     assert_eq!(list.len(), 0);
+
+    // Mess around
+    list.push_back(10);
+    list.check_invariants();
+    assert_eq!(list.len(), 1);
+    list.push_back(20);
+    list.check_invariants();
+    assert_eq!(list.len(), 2);
+    list.push_back(30);
+    list.check_invariants();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.pop_back(), Some(30));
+    list.check_invariants();
+    assert_eq!(list.len(), 2);
+    list.push_back(40);
+    list.check_invariants();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.pop_back(), Some(40));
+    list.check_invariants();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.pop_back(), Some(20));
+    list.check_invariants();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list.pop_back(), Some(10));
+    list.check_invariants();
+    assert_eq!(list.len(), 0);
+    assert_eq!(list.pop_back(), None);
+    list.check_invariants();
+    assert_eq!(list.len(), 0);
+}
+
+#[test]
+fn test_sixth_mixed() {
+    let mut list = LinkedList::new();
+    assert!(list.is_empty());
+    assert_eq!(list.front(), None);
+    assert_eq!(list.back(), None);
+
+    // Push on one end, pop from the other: behaves like a queue
+    list.push_front(1);
+    list.check_invariants();
+    list.push_front(2);
+    list.check_invariants();
+    list.push_front(3);
+    list.check_invariants();
+    assert_eq!(list.front(), Some(&3));
+    assert_eq!(list.back(), Some(&1));
+    assert_eq!(list.pop_back(), Some(1));
+    list.check_invariants();
+    assert_eq!(list.pop_back(), Some(2));
+    list.check_invariants();
+    assert_eq!(list.len(), 1);
+
+    // The single remaining node is both front and back
+    assert_eq!(list.front(), Some(&3));
+    assert_eq!(list.back(), Some(&3));
+
+    list.push_back(4);
+    list.check_invariants();
+    list.push_front(5);
+    list.check_invariants();
+    // [5, 3, 4]
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.front(), Some(&5));
+    assert_eq!(list.back(), Some(&4));
+
+    if let Some(x) = list.front_mut() {
+        *x *= 10;
+    }
+    list.check_invariants();
+    if let Some(x) = list.back_mut() {
+        *x *= 10;
+    }
+    list.check_invariants();
+    // [50, 3, 40]
+    assert_eq!(list.pop_front(), Some(50));
+    list.check_invariants();
+    assert_eq!(list.pop_back(), Some(40));
+    list.check_invariants();
+    assert_eq!(list.pop_front(), Some(3));
+    list.check_invariants();
+    assert!(list.is_empty());
+    assert_eq!(list.pop_front(), None);
+    list.check_invariants();
+    assert_eq!(list.pop_back(), None);
+    list.check_invariants();
+
+    // Last node popped from the back, list must be usable from the front again
+    list.push_back(6);
+    list.check_invariants();
+    assert_eq!(list.pop_back(), Some(6));
+    list.check_invariants();
+    list.push_front(7);
+    list.check_invariants();
+    assert_eq!(list.back(), Some(&7));
+    assert_eq!(list.pop_front(), Some(7));
+    list.check_invariants();
+    assert_eq!(list.len(), 0);
+}
+
+fn list_from(values: &[i32]) -> LinkedList<i32> {
+    let mut list = LinkedList::new();
+    for &v in values {
+        list.push_back(v);
+    }
+    list
+}
+
+#[test]
+fn test_sixth_iter() {
+    let list = list_from(&[1, 2, 3, 4]);
+
+    let mut iter = list.iter();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next_back(), Some(&3));
+    assert_eq!(iter.next(), Some(&2));
+    // Cursors met, both ends are exhausted and stay that way
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+
+    let mut sum = 0;
+    for x in &list {
+        sum += *x;
+    }
+    assert_eq!(sum, 10);
+    assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), [4, 3, 2, 1]);
+
+    let empty = LinkedList::<i32>::new();
+    assert_eq!(empty.iter().next(), None);
+    assert_eq!(empty.iter().next_back(), None);
+}
+
+#[test]
+fn test_sixth_iter_mut() {
+    let mut list = list_from(&[1, 2, 3]);
+
+    for x in &mut list {
+        *x *= 10;
+    }
+
+    let mut iter = list.iter_mut();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some(&mut 30));
+    assert_eq!(iter.next(), Some(&mut 10));
+    assert_eq!(iter.next_back(), Some(&mut 20));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    assert_eq!(list.front(), Some(&10));
+    assert_eq!(list.back(), Some(&30));
+}
+
+#[test]
+fn test_sixth_into_iter() {
+    let list = list_from(&[1, 2, 3, 4]);
+
+    let mut iter = list.into_iter();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(4));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next_back(), Some(3));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let collected: Vec<_> = list_from(&[5, 6]).into_iter().collect();
+    assert_eq!(collected, [5, 6]);
+
+    // Dropping a partially consumed IntoIter frees the rest
+    let mut iter = list_from(&[7, 8, 9]).into_iter();
+    assert_eq!(iter.next(), Some(7));
+}
+
+#[test]
+fn test_sixth_cursor_move_peek() {
+    let mut list = list_from(&[1, 2, 3, 4, 5, 6]);
+    let mut cursor = list.cursor_mut();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&mut 1));
+    assert_eq!(cursor.peek_prev(), Some(&mut 6));
+    assert_eq!(cursor.index(), None);
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 2));
+    assert_eq!(cursor.peek_next(), Some(&mut 3));
+    assert_eq!(cursor.peek_prev(), Some(&mut 1));
+    assert_eq!(cursor.index(), Some(1));
+
+    let mut cursor = list.cursor_mut();
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(&mut 6));
+    assert_eq!(cursor.peek_next(), None);
+    assert_eq!(cursor.peek_prev(), Some(&mut 5));
+    assert_eq!(cursor.index(), Some(5));
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&mut 1));
+    assert_eq!(cursor.peek_prev(), Some(&mut 6));
+    assert_eq!(cursor.index(), None);
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.index(), Some(0));
+
+    let mut empty = LinkedList::<i32>::new();
+    let mut cursor = empty.cursor_mut();
+    cursor.move_next();
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), None);
+    assert_eq!(cursor.peek_prev(), None);
+    assert_eq!(cursor.index(), None);
+}
+
+#[test]
+fn test_sixth_cursor_mut_insert_remove() {
+    let mut list = list_from(&[1, 2, 3, 4, 5, 6]);
+    let mut cursor = list.cursor_mut();
+    // Ghost: insert_before pushes back, insert_after pushes front
+    cursor.insert_before(7);
+    cursor.insert_after(0);
+    assert_eq!(cursor.index(), None);
+    cursor.move_next();
+    cursor.move_next();
+    // [0, 1, 2, 3, 4, 5, 6, 7], cursor on 1
+    cursor.insert_before(10);
+    cursor.insert_after(20);
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(cursor.peek_prev(), Some(&mut 10));
+    assert_eq!(cursor.peek_next(), Some(&mut 20));
+
+    assert_eq!(cursor.remove_current(), Some(1));
+    assert_eq!(cursor.current(), Some(&mut 20));
+    assert_eq!(cursor.index(), Some(2));
+    cursor.move_prev();
+    cursor.move_prev();
+    // Removing the front
+    assert_eq!(cursor.remove_current(), Some(0));
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(cursor.current(), Some(&mut 10));
+    cursor.move_prev();
+    cursor.move_prev();
+    // Removing the back walks onto the ghost
+    assert_eq!(cursor.remove_current(), Some(7));
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.remove_current(), None);
+
+    assert_eq!(list.len(), 7);
+    assert_eq!(
+        list.iter().copied().collect::<Vec<_>>(),
+        [10, 20, 2, 3, 4, 5, 6]
+    );
+    assert_eq!(
+        list.iter().rev().copied().collect::<Vec<_>>(),
+        [6, 5, 4, 3, 2, 20, 10]
+    );
+
+    // Drain a list down to nothing and build it back up through the cursor
+    let mut list = list_from(&[1, 2]);
+    let mut cursor = list.cursor_mut();
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(1));
+    assert_eq!(cursor.remove_current(), Some(2));
+    assert_eq!(cursor.index(), None);
+    cursor.insert_after(3);
+    cursor.move_next();
+    cursor.insert_after(4);
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), [3, 4]);
+    assert_eq!(list.front(), Some(&3));
+    assert_eq!(list.back(), Some(&4));
+}
+
+// Runs the same random cursor walk against a LinkedList and a Vec plus an
+// Option<usize> index playing the role of the cursor (None is the ghost).
+#[test]
+fn test_sixth_cursor_mut_model() {
+    for seed in 1..=64u64 {
+        let mut rng = Rng::new(seed);
+        let mut list = LinkedList::new();
+        let mut model: Vec<u64> = Vec::new();
+        let mut index: Option<usize> = None;
+
+        let mut cursor = list.cursor_mut();
+        for step in 0..200 {
+            match rng.below(6) {
+                0 => {
+                    cursor.move_next();
+                    index = match index {
+                        None if model.is_empty() => None,
+                        None => Some(0),
+                        Some(i) if i + 1 < model.len() => Some(i + 1),
+                        Some(_) => None,
+                    };
+                }
+                1 => {
+                    cursor.move_prev();
+                    index = match index {
+                        None if model.is_empty() => None,
+                        None => Some(model.len() - 1),
+                        Some(0) => None,
+                        Some(i) => Some(i - 1),
+                    };
+                }
+                2 => {
+                    let x = rng.next();
+                    cursor.insert_before(x);
+                    match index {
+                        None => model.push(x),
+                        Some(i) => {
+                            model.insert(i, x);
+                            index = Some(i + 1);
+                        }
+                    }
+                }
+                3 => {
+                    let x = rng.next();
+                    cursor.insert_after(x);
+                    match index {
+                        None => model.insert(0, x),
+                        Some(i) => model.insert(i + 1, x),
+                    }
+                }
+                4 => {
+                    let expected = index.map(|i| model.remove(i));
+                    if let Some(i) = index {
+                        if i == model.len() {
+                            index = None;
+                        }
+                    }
+                    assert_eq!(
+                        cursor.remove_current(),
+                        expected,
+                        "seed {} step {}",
+                        seed,
+                        step
+                    );
+                }
+                _ => {
+                    if let Some(x) = cursor.current() {
+                        *x = x.wrapping_add(1);
+                    }
+                    if let Some(i) = index {
+                        model[i] = model[i].wrapping_add(1);
+                    }
+                }
+            }
+
+            let next = match index {
+                None => model.first(),
+                Some(i) => model.get(i + 1),
+            };
+            let prev = match index {
+                None => model.last(),
+                Some(0) => None,
+                Some(i) => model.get(i - 1),
+            };
+            assert_eq!(cursor.index(), index, "seed {} step {}", seed, step);
+            assert_eq!(
+                cursor.current().copied(),
+                index.map(|i| model[i]),
+                "seed {} step {}",
+                seed,
+                step
+            );
+            assert_eq!(cursor.peek_next().copied(), next.copied());
+            assert_eq!(cursor.peek_prev().copied(), prev.copied());
+        }
+
+        assert_eq!(list.len(), model.len());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), model);
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            model.iter().rev().copied().collect::<Vec<_>>()
+        );
+    }
+}
+
+fn contents<A: Allocator>(list: &LinkedList<i32, A>) -> Vec<i32> {
+    list.check_invariants();
+    // Walk both directions so a broken back link can't hide behind good front links
+    let forward: Vec<i32> = list.iter().copied().collect();
+    let mut backward: Vec<i32> = list.iter().rev().copied().collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+    assert_eq!(forward.len(), list.len());
+    forward
+}
+
+#[test]
+fn test_sixth_cursor_split() {
+    let mut list = list_from(&[1, 2, 3, 4, 5, 6]);
+    let mut cursor = list.cursor_mut();
+    cursor.move_next();
+    cursor.move_next();
+    cursor.move_next();
+    // Cursor on 3
+    let before = cursor.split_before();
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(cursor.current(), Some(&mut 3));
+    assert_eq!(contents(&before), [1, 2]);
+
+    cursor.move_next();
+    // Cursor on 4
+    let after = cursor.split_after();
+    assert_eq!(cursor.index(), Some(1));
+    assert_eq!(cursor.current(), Some(&mut 4));
+    assert_eq!(contents(&after), [5, 6]);
+    assert_eq!(contents(&list), [3, 4]);
+
+    // Splitting at the very ends leaves an empty half
+    let mut list = list_from(&[1, 2, 3]);
+    let mut cursor = list.cursor_mut();
+    cursor.move_next();
+    let before = cursor.split_before();
+    assert!(before.is_empty());
+    assert_eq!(cursor.index(), Some(0));
+    cursor.move_prev();
+    cursor.move_prev();
+    let after = cursor.split_after();
+    assert!(after.is_empty());
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(contents(&list), [1, 2, 3]);
+
+    // Splitting at the ghost takes the whole list
+    let mut cursor = list.cursor_mut();
+    let all = cursor.split_after();
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.peek_next(), None);
+    assert_eq!(contents(&all), [1, 2, 3]);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn test_sixth_cursor_splice() {
+    let mut list = list_from(&[1, 2, 3]);
+    let mut cursor = list.cursor_mut();
+    cursor.move_next();
+    cursor.move_next();
+    // Cursor on 2
+    cursor.splice_before(list_from(&[10, 11]));
+    assert_eq!(cursor.index(), Some(3));
+    assert_eq!(cursor.current(), Some(&mut 2));
+    cursor.splice_after(list_from(&[20, 21]));
+    assert_eq!(cursor.index(), Some(3));
+    assert_eq!(cursor.peek_next(), Some(&mut 20));
+    assert_eq!(contents(&list), [1, 10, 11, 2, 20, 21, 3]);
+
+    // Splicing at the ends of the list
+    let mut cursor = list.cursor_mut();
+    cursor.move_next();
+    cursor.splice_before(list_from(&[0]));
+    assert_eq!(cursor.index(), Some(1));
+    cursor.move_prev();
+    cursor.move_prev();
+    cursor.move_prev();
+    cursor.splice_after(list_from(&[4]));
+    assert_eq!(cursor.index(), Some(7));
+    assert_eq!(contents(&list), [0, 1, 10, 11, 2, 20, 21, 3, 4]);
+
+    // Splicing at the ghost goes to the back (before) or front (after)
+    let mut cursor = list.cursor_mut();
+    cursor.splice_before(list_from(&[5]));
+    cursor.splice_after(list_from(&[-1]));
+    cursor.splice_after(LinkedList::new());
+    assert_eq!(cursor.index(), None);
+    assert_eq!(contents(&list), [-1, 0, 1, 10, 11, 2, 20, 21, 3, 4, 5]);
+
+    // Splicing into an empty list adopts the input
+    let mut empty = LinkedList::new();
+    let mut cursor = empty.cursor_mut();
+    cursor.splice_after(list_from(&[7, 8]));
+    assert_eq!(cursor.index(), None);
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 7));
+    assert_eq!(contents(&empty), [7, 8]);
+}
+
+#[test]
+fn test_sixth_cursor_split_splice_roundtrip() {
+    // Move every possible run out of a list and back in again
+    for at in 0..=5 {
+        let mut list = list_from(&[1, 2, 3, 4, 5]);
+        let mut cursor = list.cursor_mut();
+        for _ in 0..at {
+            cursor.move_next();
+        }
+        let tail = cursor.split_after();
+        let head = cursor.split_before();
+        cursor.splice_before(head);
+        cursor.splice_after(tail);
+        assert_eq!(contents(&list), [1, 2, 3, 4, 5]);
+    }
+}
+
+#[test]
+fn test_sixth_append() {
+    let mut list = list_from(&[1, 2, 3]);
+    let mut other = list_from(&[4, 5]);
+    list.append(&mut other);
+    assert_eq!(contents(&list), [1, 2, 3, 4, 5]);
+    assert!(other.is_empty());
+    assert_eq!(other.front(), None);
+    assert_eq!(other.back(), None);
+
+    // other is still a perfectly usable list afterwards
+    other.push_back(6);
+    list.append(&mut other);
+    assert_eq!(contents(&list), [1, 2, 3, 4, 5, 6]);
+    assert_eq!(contents(&other), []);
+
+    // Appending an empty list is a no-op, appending to one adopts the nodes
+    let mut empty = LinkedList::new();
+    list.append(&mut empty);
+    assert_eq!(contents(&list), [1, 2, 3, 4, 5, 6]);
+    empty.append(&mut list);
+    assert_eq!(contents(&empty), [1, 2, 3, 4, 5, 6]);
+    assert_eq!(contents(&list), []);
+}
+
+#[test]
+fn test_sixth_split_off() {
+    // Every split point, covering walks from both ends
+    for at in 0..=6 {
+        let mut list = list_from(&[1, 2, 3, 4, 5, 6]);
+        let rest = list.split_off(at);
+        let expected: Vec<i32> = (1..=6).collect();
+        assert_eq!(contents(&list), &expected[..at]);
+        assert_eq!(contents(&rest), &expected[at..]);
+
+        // And glue them back together
+        let mut rest = rest;
+        list.append(&mut rest);
+        assert_eq!(contents(&list), expected);
+    }
+
+    let mut empty = LinkedList::<i32>::new();
+    assert!(empty.split_off(0).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Cannot split off at a nonexistent index")]
+fn test_sixth_split_off_out_of_bounds() {
+    let mut list = list_from(&[1, 2, 3]);
+    list.split_off(4);
+}
+
+#[test]
+fn test_sixth_traits() {
+    use std::collections::HashMap;
+
+    let list: LinkedList<i32> = Default::default();
+    assert!(list.is_empty());
+
+    let list = LinkedList::from([1, 2, 3]);
+    let copy = list.clone();
+    assert_eq!(list, copy);
+    assert_eq!(contents(&copy), [1, 2, 3]);
+    assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+    assert_eq!(format!("{:?}", LinkedList::<i32>::new()), "[]");
+
+    let mut extended: LinkedList<i32> = (1..=2).collect();
+    extended.extend(&[3]);
+    assert_eq!(extended, list);
+    extended.extend(vec![4]);
+    assert_ne!(extended, list);
+
+    // Lexicographic ordering, a prefix sorts first
+    assert!(list < extended);
+    assert!(LinkedList::from([1, 3]) > extended);
+    assert_eq!(list.cmp(&copy), std::cmp::Ordering::Equal);
+    assert_eq!(
+        LinkedList::from([1.0, f64::NAN]).partial_cmp(&LinkedList::from([1.0, 2.0])),
+        None
+    );
+
+    let mut map = HashMap::new();
+    map.insert(list, "small");
+    map.insert(extended, "big");
+    assert_eq!(map.get(&LinkedList::from([1, 2, 3])), Some(&"small"));
+    assert_eq!(map.get(&LinkedList::from([1, 2, 3, 4])), Some(&"big"));
+    assert_eq!(map.get(&LinkedList::new()), None);
+
+    // The length is part of the hash, so nested lists that flatten to the
+    // same elements still hash differently.
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+    let a = LinkedList::from([LinkedList::from([1]), LinkedList::from([2, 3])]);
+    let b = LinkedList::from([LinkedList::from([1, 2]), LinkedList::from([3])]);
+    assert_ne!(a, b);
+    assert_ne!(hash_of(&a), hash_of(&b));
+}
+
+#[test]
+fn test_sixth_send_sync() {
+    use std::thread;
+
+    // Move a list into a thread and get it back out
+    let list = LinkedList::from([1, 2, 3]);
+    let list = thread::spawn(move || {
+        let mut list = list;
+        list.push_back(4);
+        list
+    })
+    .join()
+    .unwrap();
+    assert_eq!(contents(&list), [1, 2, 3, 4]);
+
+    // Share it between scoped threads
+    let sums: Vec<i32> = thread::scope(|s| {
+        let handles: Vec<_> = (0..2)
+            .map(|_| s.spawn(|| list.iter().sum::<i32>()))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    assert_eq!(sums, [10, 10]);
+}
+
+#[test]
+fn test_sixth_sort() {
+    let mut empty = LinkedList::<i32>::new();
+    empty.sort();
+    assert_eq!(contents(&empty), []);
+
+    let mut list = LinkedList::from([5, 1, 4, 2, 3]);
+    list.sort();
+    assert_eq!(contents(&list), [1, 2, 3, 4, 5]);
+    list.sort_by(|a, b| b.cmp(a));
+    assert_eq!(contents(&list), [5, 4, 3, 2, 1]);
+
+    // Random lists of every small length and some odd sized larger ones
+    let mut rng = Rng::new(1);
+    for len in (0..40).chain([63, 64, 65, 257]) {
+        let values: Vec<i32> = (0..len).map(|_| rng.below(20) as i32).collect();
+        let mut list: LinkedList<i32> = values.iter().copied().collect();
+        let mut expected = values.clone();
+        list.sort();
+        expected.sort();
+        assert_eq!(contents(&list), expected, "len {}", len);
+    }
+}
+
+#[test]
+fn test_sixth_sort_stable() {
+    let mut rng = Rng::new(2);
+    let values: Vec<(usize, usize)> = (0..200).map(|i| (rng.below(8), i)).collect();
+    let mut list: LinkedList<(usize, usize)> = values.iter().copied().collect();
+    let mut expected = values.clone();
+    list.sort_by_key(|&(key, _)| key);
+    expected.sort_by_key(|&(key, _)| key);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn test_sixth_sort_nodes_do_not_move() {
+    let mut list = LinkedList::from([3, 1, 2]);
+    let before: Vec<*const i32> = {
+        let mut addrs: Vec<(i32, *const i32)> =
+            list.iter().map(|x| (*x, x as *const i32)).collect();
+        addrs.sort();
+        addrs.into_iter().map(|(_, p)| p).collect()
+    };
+    list.sort();
+    let after: Vec<*const i32> = list.iter().map(|x| x as *const i32).collect();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn test_sixth_sort_panic_safety() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let values: Vec<i32> = (0..50).rev().collect();
+    for panic_at in [0, 1, 7, 30, 100] {
+        let mut list: LinkedList<i32> = values.iter().copied().collect();
+        let mut calls = 0;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            list.sort_by(|a, b| {
+                if calls == panic_at {
+                    panic!("comparator gave up");
+                }
+                calls += 1;
+                a.cmp(b)
+            });
+        }));
+        assert!(result.is_err());
+
+        // The list is still well formed and nothing was lost or duplicated
+        let mut seen = contents(&list);
+        seen.sort();
+        let mut expected = values.clone();
+        expected.sort();
+        assert_eq!(seen, expected);
+
+        // ...and still usable
+        list.push_front(-1);
+        list.sort();
+        assert_eq!(list.pop_front(), Some(-1));
+        assert_eq!(contents(&list), expected);
+    }
+}
+
+#[test]
+fn test_sixth_retain() {
+    let mut list: LinkedList<i32> = (1..=10).collect();
+    list.retain(|x| x % 2 == 0);
+    assert_eq!(contents(&list), [2, 4, 6, 8, 10]);
+
+    list.retain_mut(|x| {
+        *x *= 10;
+        *x != 20 && *x != 100
+    });
+    assert_eq!(contents(&list), [40, 60, 80]);
+
+    list.retain(|_| false);
+    assert_eq!(contents(&list), []);
+    list.retain(|_| false);
+    assert_eq!(contents(&list), []);
+}
+
+#[test]
+fn test_sixth_extract_if() {
+    let mut list: LinkedList<i32> = (1..=10).collect();
+    let evens: Vec<i32> = list.extract_if(|x| *x % 2 == 0).collect();
+    assert_eq!(evens, [2, 4, 6, 8, 10]);
+    assert_eq!(contents(&list), [1, 3, 5, 7, 9]);
+
+    // Lazy: dropping the iterator early leaves the rest alone
+    assert_eq!(list.extract_if(|x| *x > 2).next(), Some(3));
+    assert_eq!(contents(&list), [1, 5, 7, 9]);
+
+    // Removing the front and the back
+    let all: Vec<i32> = list.extract_if(|x| *x == 1 || *x == 9).collect();
+    assert_eq!(all, [1, 9]);
+    assert_eq!(contents(&list), [5, 7]);
+
+    let mut extract = list.extract_if(|_| true);
+    assert_eq!(extract.next(), Some(5));
+    assert_eq!(extract.next(), Some(7));
+    assert_eq!(extract.next(), None);
+    assert_eq!(extract.next(), None);
+    assert_eq!(contents(&list), []);
+}
+
+#[test]
+fn test_sixth_dedup() {
+    let mut list = LinkedList::from([1, 1, 2, 3, 3, 3, 1, 4, 4]);
+    list.dedup();
+    assert_eq!(contents(&list), [1, 2, 3, 1, 4]);
+
+    let mut list = LinkedList::from([10, 11, 20, 25, 31, 39, 40]);
+    list.dedup_by_key(|x| *x / 10);
+    assert_eq!(contents(&list), [10, 20, 31, 40]);
+
+    // Candidates are compared with the last *kept* element
+    let mut list = LinkedList::from([1, 2, 3, 4, 5, 6]);
+    list.dedup_by(|cur, kept| *cur - *kept < 3);
+    assert_eq!(contents(&list), [1, 4]);
+
+    let mut empty = LinkedList::<i32>::new();
+    empty.dedup();
+    assert_eq!(contents(&empty), []);
+}
+
+#[test]
+fn test_sixth_filter_panic_safety() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    fn boom(x: &i32) {
+        if *x == 5 {
+            panic!("predicate gave up");
+        }
+    }
+
+    let mut list: LinkedList<i32> = (1..=8).collect();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        list.retain(|x| {
+            boom(x);
+            x % 2 == 1
+        })
+    }));
+    assert!(result.is_err());
+    // Everything before the panic was filtered, everything after is untouched
+    assert_eq!(contents(&list), [1, 3, 5, 6, 7, 8]);
+
+    let mut list: LinkedList<i32> = (1..=8).collect();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        list.extract_if(|x| {
+            boom(x);
+            *x % 2 == 0
+        })
+        .count()
+    }));
+    assert!(result.is_err());
+    assert_eq!(contents(&list), [1, 3, 5, 6, 7, 8]);
+
+    let mut list = LinkedList::from([1, 1, 2, 5, 5, 6, 6]);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        list.dedup_by(|cur, kept| {
+            boom(cur);
+            cur == kept
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(contents(&list), [1, 2, 5, 5, 6, 6]);
+}
+
+#[test]
+fn test_sixth_index_access() {
+    let mut list: LinkedList<i32> = (0..7).collect();
+    // Reads from both halves, so both walking directions get used
+    for i in 0..7 {
+        assert_eq!(list.get(i), Some(&(i as i32)));
+    }
+    assert_eq!(list.get(7), None);
+    assert_eq!(LinkedList::<i32>::new().get(0), None);
+
+    *list.get_mut(1).unwrap() = 10;
+    *list.get_mut(5).unwrap() = 50;
+    assert_eq!(list.get_mut(7), None);
+    assert_eq!(contents(&list), [0, 10, 2, 3, 4, 50, 6]);
+
+    list.insert(0, -1);
+    list.check_invariants();
+    list.insert(8, 7);
+    list.check_invariants();
+    list.insert(2, 100);
+    list.check_invariants();
+    list.insert(7, 200);
+    list.check_invariants();
+    assert_eq!(contents(&list), [-1, 0, 100, 10, 2, 3, 4, 200, 50, 6, 7]);
+
+    assert_eq!(list.remove(11), None);
+    list.check_invariants();
+    assert_eq!(list.remove(0), Some(-1));
+    list.check_invariants();
+    assert_eq!(list.remove(9), Some(7));
+    list.check_invariants();
+    assert_eq!(list.remove(1), Some(100));
+    list.check_invariants();
+    assert_eq!(list.remove(5), Some(200));
+    list.check_invariants();
+    assert_eq!(contents(&list), [0, 10, 2, 3, 4, 50, 6]);
+
+    let mut list = LinkedList::new();
+    list.insert(0, 1);
+    list.check_invariants();
+    assert_eq!(list.remove(0), Some(1));
+    list.check_invariants();
+    assert_eq!(list.remove(0), None);
+    list.check_invariants();
+    assert_eq!(contents(&list), []);
+}
+
+#[test]
+#[should_panic(expected = "insertion index (is 4) should be <= len (is 3)")]
+fn test_sixth_insert_out_of_bounds() {
+    let mut list = LinkedList::from([1, 2, 3]);
+    list.insert(4, 4);
+}
+
+mod allocators {
+    use super::contents;
+    use code::sixth::{Allocator, Global, LinkedList};
+    use std::alloc::Layout;
+    use std::cell::Cell;
+    use std::ptr::NonNull;
+    use std::rc::Rc;
+
+    // Forwards to Global and keeps track of how many blocks are live. Clones
+    // share the counters. Only clones count as the same allocator, so every
+    // block is freed by an instance that counted it.
+    #[derive(Clone, Default)]
+    struct Counting {
+        live: Rc<Cell<isize>>,
+        total: Rc<Cell<usize>>,
+    }
+
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+            self.live.set(self.live.get() + 1);
+            self.total.set(self.total.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.live.set(self.live.get() - 1);
+            Global.deallocate(ptr, layout)
+        }
+
+        fn same_allocator(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.live, &other.live)
+        }
+    }
+
+    // Hands out memory from one fixed block and never frees anything until
+    // the arena itself goes away.
+    struct Bump {
+        start: NonNull<u8>,
+        layout: Layout,
+        used: Cell<usize>,
+    }
+
+    impl Bump {
+        fn new(size: usize) -> Self {
+            let layout = Layout::from_size_align(size, 16).unwrap();
+            Bump {
+                start: Global.allocate(layout).unwrap(),
+                layout,
+                used: Cell::new(0),
+            }
+        }
+
+        fn contains<T>(&self, ptr: *const T) -> bool {
+            let start = self.start.as_ptr() as usize;
+            (start..start + self.layout.size()).contains(&(ptr as usize))
+        }
+    }
+
+    impl Drop for Bump {
+        fn drop(&mut self) {
+            unsafe { Global.deallocate(self.start, self.layout) }
+        }
+    }
+
+    // The borrow keeps the arena alive for as long as any list using it.
+    unsafe impl Allocator for &Bump {
+        fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+            let start = self.start.as_ptr() as usize + self.used.get();
+            let aligned = (start + layout.align() - 1) & !(layout.align() - 1);
+            let end = aligned + layout.size();
+            if end > self.start.as_ptr() as usize + self.layout.size() {
+                return None;
+            }
+            self.used.set(end - self.start.as_ptr() as usize);
+            NonNull::new(aligned as *mut u8)
+        }
+
+        unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+
+        fn same_allocator(&self, other: &Self) -> bool {
+            std::ptr::eq(*self, *other)
+        }
+    }
+
+    // Fixed size slots carved out of one block. Freed slots go on the slab's
+    // own free list, chained through their first word, and get handed out
+    // again before any fresh ones.
+    struct Slab {
+        start: NonNull<u8>,
+        slots: usize,
+        used: Cell<usize>,
+        free: Cell<Option<NonNull<u8>>>,
+        live: Cell<usize>,
+    }
+
+    impl Slab {
+        const SLOT: usize = 64;
+
+        fn new(slots: usize) -> Self {
+            Slab {
+                start: Global.allocate(Self::layout(slots)).unwrap(),
+                slots,
+                used: Cell::new(0),
+                free: Cell::new(None),
+                live: Cell::new(0),
+            }
+        }
+
+        fn layout(slots: usize) -> Layout {
+            Layout::from_size_align(Self::SLOT * slots, Self::SLOT).unwrap()
+        }
+
+        fn contains<T>(&self, ptr: *const T) -> bool {
+            let start = self.start.as_ptr() as usize;
+            (start..start + Self::SLOT * self.slots).contains(&(ptr as usize))
+        }
+    }
+
+    impl Drop for Slab {
+        fn drop(&mut self) {
+            unsafe { Global.deallocate(self.start, Self::layout(self.slots)) }
+        }
+    }
+
+    unsafe impl Allocator for &Slab {
+        fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+            if layout.size() > Slab::SLOT || layout.align() > Slab::SLOT {
+                return None;
+            }
+            let slot = if let Some(slot) = self.free.get() {
+                self.free
+                    .set(unsafe { slot.cast::<Option<NonNull<u8>>>().as_ptr().read() });
+                slot
+            } else if self.used.get() < self.slots {
+                let slot = unsafe { self.start.as_ptr().add(Slab::SLOT * self.used.get()) };
+                self.used.set(self.used.get() + 1);
+                NonNull::new(slot)?
+            } else {
+                return None;
+            };
+            self.live.set(self.live.get() + 1);
+            Some(slot)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, _layout: Layout) {
+            // A node from another slab would end up on our free list
+            assert!(self.contains(ptr.as_ptr()), "freed into the wrong slab");
+            ptr.cast::<Option<NonNull<u8>>>()
+                .as_ptr()
+                .write(self.free.get());
+            self.free.set(Some(ptr));
+            self.live.set(self.live.get() - 1);
+        }
+
+        fn same_allocator(&self, other: &Self) -> bool {
+            std::ptr::eq(*self, *other)
+        }
+    }
+
+    #[test]
+    fn test_sixth_counting_allocator() {
+        let alloc = Counting::default();
+        let live = alloc.live.clone();
+        let total = alloc.total.clone();
+        {
+            let mut list = LinkedList::new_in(alloc);
+            list.extend(0..10);
+            assert_eq!(live.get(), 10);
+            assert_eq!(list.pop_front(), Some(0));
+            assert_eq!(list.pop_back(), Some(9));
+            assert_eq!(live.get(), 8);
+
+            let mut cursor = list.cursor_mut();
+            cursor.move_next();
+            cursor.insert_after(100);
+            cursor.remove_current();
+            let mut rest = cursor.split_after();
+            assert_eq!(live.get(), 8);
+
+            let copy = rest.clone();
+            assert_eq!(live.get(), 15);
+            list.append(&mut rest);
+            drop(copy);
+            assert_eq!(live.get(), 8);
+            assert_eq!(contents(&list), [100, 2, 3, 4, 5, 6, 7, 8]);
+
+            let tail = list.split_off(4);
+            let mut iter = tail.into_iter();
+            assert_eq!(iter.next(), Some(5));
+            assert_eq!(live.get(), 7);
+        }
+        assert_eq!(live.get(), 0);
+        assert_eq!(total.get(), 18);
+    }
+
+    // Lists made with separate new_in calls can be joined, and the nodes that
+    // moved are then freed by the allocator of the list they moved into.
+    #[test]
+    fn test_sixth_mixed_allocators() {
+        use std::panic::{self, AssertUnwindSafe};
+
+        let a = Counting::default();
+        let b = Counting::default();
+        let (a_live, b_live) = (a.live.clone(), b.live.clone());
+
+        let mut list = LinkedList::new_in(a);
+        list.extend(0..3);
+        let mut other = LinkedList::new_in(b.clone());
+        other.extend(3..5);
+
+        // a couldn't free b's nodes, so they stay where they are
+        let result = panic::catch_unwind(AssertUnwindSafe(|| list.append(&mut other)));
+        assert!(result.is_err());
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            list.cursor_mut().splice_after(other.split_off(1))
+        }));
+        assert!(result.is_err());
+        assert_eq!(contents(&list), [0, 1, 2]);
+        assert_eq!(contents(&other), [3]);
+        assert_eq!((a_live.get(), b_live.get()), (3, 1));
+
+        // Clones of b are still fine
+        let mut spliced = LinkedList::new_in(b);
+        spliced.extend(5..7);
+        other.cursor_mut().splice_before(spliced);
+        assert_eq!(contents(&other), [3, 5, 6]);
+        drop(list);
+        drop(other);
+        assert_eq!((a_live.get(), b_live.get()), (0, 0));
+    }
+
+    #[test]
+    fn test_sixth_slab_allocator() {
+        let a = Slab::new(8);
+        let b = Slab::new(8);
+
+        // Fill every slot in a from two lists and put them together
+        let mut list = LinkedList::new_in(&a);
+        list.extend(0..4);
+        let mut more = LinkedList::new_in(&a);
+        more.extend(4..8);
+        list.append(&mut more);
+        assert_eq!(a.live.get(), 8);
+
+        // The slab is full, so these pushes only work because the pops gave
+        // their slots back to a
+        assert_eq!(list.pop_front(), Some(0));
+        assert_eq!(list.pop_back(), Some(7));
+        assert_eq!(a.live.get(), 6);
+        list.push_back(8);
+        list.push_front(9);
+        assert!(list.iter().all(|x| a.contains(x)));
+
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        let mut tail = cursor.split_after();
+        tail.retain(|x| x % 2 == 0);
+        list.cursor_mut().splice_before(tail);
+        assert_eq!(contents(&list), [9, 2, 4, 6, 8]);
+        assert_eq!(a.live.get(), 5);
+
+        let mut other = LinkedList::new_in(&b);
+        other.extend([10, 11]);
+        assert!(other.iter().all(|x| b.contains(x)));
+        drop(list);
+        drop(other);
+        assert_eq!((a.live.get(), b.live.get()), (0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot append a list from a different allocator")]
+    fn test_sixth_slab_append_other_slab() {
+        let a = Slab::new(4);
+        let b = Slab::new(4);
+        let mut list = LinkedList::new_in(&a);
+        list.push_back(1);
+        let mut other = LinkedList::new_in(&b);
+        other.push_back(2);
+        list.append(&mut other);
+    }
+
+    #[test]
+    fn test_sixth_bump_allocator() {
+        let arena = Bump::new(4096);
+        let mut list = LinkedList::new_in(&arena);
+        for i in 0..20 {
+            list.push_back(i);
+        }
+        list.retain(|x| x % 3 != 0);
+        list.sort_by(|a, b| b.cmp(a));
+        assert!(list.iter().all(|x| arena.contains(x)));
+        assert_eq!(list.len(), 13);
+        assert_eq!(list.front(), Some(&19));
+        assert_eq!(list.back(), Some(&1));
+    }
+
+    #[test]
+    fn test_sixth_recycling() {
+        let alloc = Counting::default();
+        let live = alloc.live.clone();
+        let total = alloc.total.clone();
+        {
+            let mut list = LinkedList::new_in(alloc);
+            assert!(!list.is_recycling());
+            list.set_recycling(true);
+            list.extend(0..4);
+            assert_eq!(total.get(), 4);
+
+            // Churn through the queue, the same four nodes get used over and over
+            for i in 4..1000 {
+                assert_eq!(list.pop_front(), Some(i - 4));
+                list.push_back(i);
+            }
+            assert_eq!(total.get(), 4);
+            assert_eq!(contents(&list), [996, 997, 998, 999]);
+
+            // Removals through any path go back to the pool too
+            assert_eq!(list.remove(1), Some(997));
+            list.retain(|x| *x != 998);
+            assert_eq!(list.len(), 2);
+            assert_eq!(list.capacity(), 4);
+            list.push_front(1);
+            list.push_front(2);
+            assert_eq!(total.get(), 4);
+
+            // Pool survives splitting the list apart and splicing it back
+            let mut tail = list.split_off(0);
+            assert_eq!(list.capacity(), 0);
+            // Whatever the split index, the new list recycles like ours does
+            assert!(tail.is_recycling());
+            let empty = tail.split_off(tail.len());
+            assert!(empty.is_recycling());
+            assert!(empty.is_empty());
+            let mut cursor = list.cursor_mut();
+            cursor.splice_after(tail.split_off(0));
+            assert_eq!(contents(&list), [2, 1, 996, 999]);
+
+            list.clear();
+            assert_eq!(list.capacity(), 4);
+            assert_eq!(live.get(), 4);
+            list.shrink_to_fit();
+            assert_eq!(list.capacity(), 0);
+            assert_eq!(live.get(), 0);
+        }
+        assert_eq!(live.get(), 0);
+    }
+
+    #[test]
+    fn test_sixth_reserve() {
+        let alloc = Counting::default();
+        let live = alloc.live.clone();
+        let total = alloc.total.clone();
+        {
+            let mut list = LinkedList::new_in(alloc);
+            list.reserve(8);
+            assert_eq!(list.capacity(), 8);
+            assert_eq!(live.get(), 8);
+            list.reserve(3);
+            assert_eq!(total.get(), 8);
+
+            // Reserved nodes get used even with recycling off...
+            list.extend(0..8);
+            assert_eq!(total.get(), 8);
+            list.push_back(8);
+            assert_eq!(total.get(), 9);
+
+            // ...but without recycling pops free nodes straight away
+            list.pop_back();
+            list.pop_front();
+            assert_eq!(live.get(), 7);
+            assert_eq!(list.capacity(), 7);
+
+            // Drop frees both the nodes and whatever is left in the pool
+            list.set_recycling(true);
+            list.pop_back();
+            list.reserve(5);
+            assert_eq!(live.get(), 11);
+        }
+        assert_eq!(live.get(), 0);
+    }
+
+    #[test]
+    fn test_sixth_default_allocator() {
+        // Plain LinkedList<T> still means LinkedList<T, Global>
+        let list: LinkedList<i32, Global> = LinkedList::from([1, 2]);
+        assert_eq!(contents(&list), [1, 2]);
+    }
 }
@@ -0,0 +1,221 @@
+use code::{fifth, fourth, second, sixth, third};
+
+// The pieces of a to_dot() picture that the tests care about.
+struct Graph {
+    // Element nodes, with their labels
+    nodes: Vec<(String, String)>,
+    // (from, to, label)
+    edges: Vec<(String, String, String)>,
+}
+
+impl Graph {
+    fn edges_labelled(&self, label: &str) -> usize {
+        self.edges.iter().filter(|(_, _, l)| l == label).count()
+    }
+
+    fn target_of(&self, from: &str, label: &str) -> Option<&str> {
+        self.edges
+            .iter()
+            .find(|(f, _, l)| f == from && l == label)
+            .map(|(_, to, _)| to.as_str())
+    }
+}
+
+// Pulls the quoted string starting at `s` and returns it with the rest.
+fn quoted(s: &str) -> (String, &str) {
+    let s = s.strip_prefix('"').expect("expected a quoted string");
+    let mut out = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => out.push(chars.next().unwrap().1),
+            '"' => return (out, &s[i + 1..]),
+            _ => out.push(c),
+        }
+    }
+    panic!("unterminated string in {:?}", s);
+}
+
+// Parses what the lists emit and checks that it is well formed: one
+// statement per line, every node declared once, every edge between declared
+// nodes.
+fn parse(dot: &str) -> Graph {
+    let mut lines = dot.lines();
+    assert!(lines.next().unwrap().starts_with("digraph \""), "{}", dot);
+    assert!(dot.ends_with("}\n"), "{}", dot);
+
+    let mut declared = Vec::new();
+    let mut graph = Graph {
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+    for line in lines {
+        let line = line.trim();
+        if !line.starts_with('"') {
+            continue;
+        }
+        let (from, rest) = quoted(line);
+        if let Some(rest) = rest.strip_prefix(" -> ") {
+            let (to, rest) = quoted(rest);
+            let (label, _) = quoted(rest.strip_prefix(" [label=").unwrap());
+            graph.edges.push((from, to, label));
+        } else {
+            assert!(!declared.contains(&from), "{} declared twice", from);
+            declared.push(from.clone());
+            let (label, _) = quoted(
+                rest.strip_prefix(" [label=")
+                    .unwrap_or_else(|| rest.strip_prefix(" [shape=plaintext, label=").unwrap()),
+            );
+            if !rest.contains("plaintext") {
+                graph.nodes.push((from, label));
+            }
+        }
+    }
+    for (from, to, _) in &graph.edges {
+        assert!(declared.contains(from), "edge from undeclared {}", from);
+        assert!(declared.contains(to), "edge to undeclared {}", to);
+    }
+    graph
+}
+
+#[test]
+fn second_to_dot() {
+    let mut list = second::List::new();
+    assert_eq!(parse(&list.to_dot()).nodes.len(), 0);
+
+    list.push(1);
+    list.push(2);
+    list.push(3);
+    let graph = parse(&list.to_dot());
+    assert_eq!(graph.nodes.len(), 3);
+    assert_eq!(graph.edges_labelled("head"), 1);
+    assert_eq!(graph.edges_labelled("next"), 2);
+
+    // Nodes are named after their addresses and list the element
+    let head = graph.target_of("list", "head").unwrap();
+    assert!(head.starts_with("0x"));
+    let (_, label) = graph.nodes.iter().find(|(id, _)| id == head).unwrap();
+    assert_eq!(label, &format!("{{{}|elem: 3}}", head));
+}
+
+#[test]
+fn to_dot_escapes_elements() {
+    let mut list = second::List::new();
+    list.push("a \"quoted\" {record|label} <port>");
+    let graph = parse(&list.to_dot());
+    // Once unescaped, the label is exactly the Debug output
+    assert!(graph.nodes[0]
+        .1
+        .ends_with("|elem: \"a \\\"quoted\\\" {record|label} <port>\"}"));
+}
+
+#[test]
+fn third_to_dot_shares_tails() {
+    let base = third::List::new().prepend(1).prepend(2).prepend(3);
+    let graph = parse(&base.to_dot());
+    assert_eq!(graph.nodes.len(), 3);
+    assert!(graph
+        .nodes
+        .iter()
+        .all(|(_, label)| label.ends_with("|rc: 1}")));
+
+    let a = base.prepend(10);
+    let b = base.prepend(20);
+    let tail = base.tail();
+    let graph = parse(&third::versions_to_dot(&[
+        ("base", &base),
+        ("a", &a),
+        ("b", &b),
+        ("tail", &tail),
+    ]));
+    // The shared nodes are only drawn once
+    assert_eq!(graph.nodes.len(), 5);
+    // Every version points into the same chain
+    let shared = graph.target_of("base", "head").unwrap();
+    assert_eq!(
+        graph.target_of(graph.target_of("a", "head").unwrap(), "next"),
+        Some(shared)
+    );
+    assert_eq!(
+        graph.target_of(graph.target_of("b", "head").unwrap(), "next"),
+        Some(shared)
+    );
+    assert_eq!(
+        graph.target_of(shared, "next"),
+        graph.target_of("tail", "head")
+    );
+    // base, a's node and b's node all hold the head of base
+    let (_, label) = graph.nodes.iter().find(|(id, _)| id == shared).unwrap();
+    assert!(label.ends_with("|rc: 3}"), "{}", label);
+}
+
+#[test]
+fn fourth_to_dot() {
+    let mut list = fourth::List::new();
+    list.push_back(1);
+    let graph = parse(&list.to_dot());
+    assert_eq!(
+        graph.target_of("list", "head"),
+        graph.target_of("list", "tail")
+    );
+    // Held by head and tail
+    assert!(graph.nodes[0].1.ends_with("|rc: 2}"));
+
+    list.push_back(2);
+    list.push_back(3);
+    list.push_front(0);
+    let graph = parse(&list.to_dot());
+    assert_eq!(graph.nodes.len(), 4);
+    assert_eq!(graph.edges_labelled("next"), 3);
+    assert_eq!(graph.edges_labelled("prev"), 3);
+    // Every node has exactly two owners: neighbours or the list itself
+    assert!(graph
+        .nodes
+        .iter()
+        .all(|(_, label)| label.ends_with("|rc: 2}")));
+    // Drawing doesn't leave any borrows or extra references behind
+    assert_eq!(list.pop_back(), Some(3));
+    list.check_invariants();
+}
+
+#[test]
+fn fifth_to_dot() {
+    let mut list = fifth::raw_pointers::List::new();
+    let graph = parse(&list.to_dot());
+    assert!(graph.edges.is_empty());
+
+    list.push(1);
+    list.push(2);
+    let graph = parse(&list.to_dot());
+    assert_eq!(graph.nodes.len(), 2);
+    let head = graph.target_of("list", "head").unwrap();
+    assert_eq!(
+        graph.target_of(head, "next"),
+        graph.target_of("list", "tail")
+    );
+}
+
+#[test]
+fn sixth_to_dot() {
+    let mut list: sixth::LinkedList<i32> = (0..4).collect();
+    let graph = parse(&list.to_dot());
+    assert_eq!(graph.nodes.len(), 4);
+    assert_eq!(graph.edges_labelled("back"), 4);
+    assert_eq!(graph.edges_labelled("front"), 4);
+
+    // Spare nodes hang off the pool, without elements
+    list.set_recycling(true);
+    list.pop_front();
+    list.reserve(3);
+    let graph = parse(&list.to_dot());
+    assert_eq!(graph.nodes.len(), 6);
+    assert_eq!(
+        graph
+            .nodes
+            .iter()
+            .filter(|(_, label)| label.ends_with("|(empty)}"))
+            .count(),
+        3
+    );
+    assert!(graph.target_of("pool", "back").is_some());
+}
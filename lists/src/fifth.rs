@@ -1,4 +1,5 @@
 use std::ptr;
+use traits::Queue;
 
 pub struct List<T> {
     head: Link<T>,
@@ -88,6 +89,14 @@ impl<T> List<T> {
     }
 }
 
+impl<T> Queue<T> for List<T> {
+    type Peek<'a> = &'a T where T: 'a;
+
+    fn push_back(&mut self, elem: T) { List::push(self, elem) }
+    fn pop_front(&mut self) -> Option<T> { List::pop(self) }
+    fn peek_front(&self) -> Option<&T> { List::peek(self) }
+}
+
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
         while let Some(_) = self.pop() { }
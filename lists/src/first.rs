@@ -1,4 +1,5 @@
 use std::mem;
+use traits::Stack;
 
 pub struct List {
     head: Link,
@@ -39,6 +40,19 @@ impl List {
     }
 }
 
+impl Stack<i32> for List {
+    type Peek<'a> = &'a i32;
+
+    fn push(&mut self, elem: i32) { List::push(self, elem) }
+    fn pop(&mut self) -> Option<i32> { List::pop(self) }
+    fn peek(&self) -> Option<&i32> {
+        match self.head {
+            Link::Empty => None,
+            Link::More(ref node) => Some(&node.elem),
+        }
+    }
+}
+
 impl Drop for List {
     fn drop(&mut self) {
         let mut cur_link = mem::replace(&mut self.head, Link::Empty);
@@ -1,5 +1,6 @@
 use std::rc::Rc;
 use std::cell::{Ref, RefMut, RefCell};
+use traits::{Deque, Queue, Stack};
 
 pub struct List<T> {
     head: Link<T>,
@@ -119,6 +120,28 @@ impl<T> List<T> {
     }
 }
 
+impl<T> Stack<T> for List<T> {
+    type Peek<'a> = Ref<'a, T> where T: 'a;
+
+    fn push(&mut self, elem: T) { List::push_front(self, elem) }
+    fn pop(&mut self) -> Option<T> { List::pop_front(self) }
+    fn peek(&self) -> Option<Ref<'_, T>> { List::peek_front(self) }
+}
+
+impl<T> Queue<T> for List<T> {
+    type Peek<'a> = Ref<'a, T> where T: 'a;
+
+    fn push_back(&mut self, elem: T) { List::push_back(self, elem) }
+    fn pop_front(&mut self) -> Option<T> { List::pop_front(self) }
+    fn peek_front(&self) -> Option<Ref<'_, T>> { List::peek_front(self) }
+}
+
+impl<T> Deque<T> for List<T> {
+    fn push_front(&mut self, elem: T) { List::push_front(self, elem) }
+    fn pop_back(&mut self) -> Option<T> { List::pop_back(self) }
+    fn peek_back(&self) -> Option<Ref<'_, T>> { List::peek_back(self) }
+}
+
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
         while self.pop_front().is_some() {}
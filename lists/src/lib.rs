@@ -4,5 +4,10 @@ pub mod third;
 pub mod fourth;
 pub mod fifth;
 
+pub mod traits;
+#[cfg(feature = "viz")]
+#[doc(hidden)]
+pub mod viz;
+
 pub mod silly1;
 pub mod silly2;
\ No newline at end of file
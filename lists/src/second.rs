@@ -1,3 +1,5 @@
+use traits::Stack;
+
 pub struct List<T> {
     head: Link<T>,
 }
@@ -51,6 +53,14 @@ impl<T> List<T> {
     }
 }
 
+impl<T> Stack<T> for List<T> {
+    type Peek<'a> = &'a T where T: 'a;
+
+    fn push(&mut self, elem: T) { List::push(self, elem) }
+    fn pop(&mut self) -> Option<T> { List::pop(self) }
+    fn peek(&self) -> Option<&T> { List::peek(self) }
+}
+
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
         let mut cur_link = self.head.take();
@@ -1,3 +1,5 @@
+use traits;
+
 pub struct List<T> {
     left: Stack<T>,
     right: Stack<T>,
@@ -30,6 +32,40 @@ impl<T> List<T> {
     }
 }
 
+// Graphviz picture of both stacks, see viz. Each stack's head is the node
+// right next to the cursor, so the left one is drawn backwards.
+#[cfg(feature = "viz")]
+impl<T: ::std::fmt::Debug> List<T> {
+    pub fn to_dot(&self) -> String {
+        use viz::{self, Dot};
+
+        let mut dot = Dot::new("silly1::List");
+        dot.handle("list", "List (cursor)");
+        for &(side, stack) in &[("left", &self.left), ("right", &self.right)] {
+            let (mut from, mut label) = ("list".to_string(), side);
+            let mut link = stack.head.as_ref();
+            while let Some(node) = link {
+                let id = viz::id(&**node);
+                dot.node(&id, &[format!("elem: {}", viz::elem(&node.elem))]);
+                dot.edge(&from, &id, label);
+                from = id;
+                label = "next";
+                link = node.next.as_ref();
+            }
+        }
+        dot.finish()
+    }
+}
+
+// Pushing and popping at the cursor, from the left side.
+impl<T> traits::Stack<T> for List<T> {
+    type Peek<'a> = &'a T where T: 'a;
+
+    fn push(&mut self, elem: T) { self.push_left(elem) }
+    fn pop(&mut self) -> Option<T> { self.pop_left() }
+    fn peek(&self) -> Option<&T> { self.peek_left() }
+}
+
 
 
 
@@ -93,6 +129,14 @@ impl<T> Stack<T> {
     }
 }
 
+impl<T> traits::Stack<T> for Stack<T> {
+    type Peek<'a> = &'a T where T: 'a;
+
+    fn push(&mut self, elem: T) { Stack::push(self, elem) }
+    fn pop(&mut self) -> Option<T> { Stack::pop(self) }
+    fn peek(&self) -> Option<&T> { Stack::peek(self) }
+}
+
 impl<T> Drop for Stack<T> {
     fn drop(&mut self) {
         let mut cur_link = self.head.take();
@@ -138,6 +182,21 @@ mod test {
         assert_eq!(list.pop_left(), None);
 
     }
+
+    #[cfg(feature = "viz")]
+    #[test]
+    fn to_dot() {
+        let mut list = List::new();
+        list.push_left(1); list.push_left(2); list.push_right(3);
+
+        let dot = list.to_dot();
+        assert!(dot.starts_with("digraph \"silly1::List\" {"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches("elem: ").count(), 3);
+        assert!(dot.contains("[label=\"left\"]"));
+        assert!(dot.contains("[label=\"right\"]"));
+        assert_eq!(dot.matches("[label=\"next\"]").count(), 1);
+    }
 }
 
 
@@ -1,4 +1,5 @@
 use std::rc::Rc;
+use traits::Stack;
 
 pub struct List<T> {
     head: Link<T>,
@@ -36,6 +37,31 @@ impl<T> List<T> {
     }
 }
 
+// Other lists may share our nodes, so pop can only move the element out when
+// it holds the last reference. Otherwise it clones and leaves the node be.
+impl<T: Clone> Stack<T> for List<T> {
+    type Peek<'a> = &'a T where T: 'a;
+
+    fn push(&mut self, elem: T) {
+        *self = self.prepend(elem);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.head.take().map(|node| match Rc::try_unwrap(node) {
+            Ok(node) => {
+                self.head = node.next;
+                node.elem
+            }
+            Err(shared) => {
+                self.head = shared.next.clone();
+                shared.elem.clone()
+            }
+        })
+    }
+
+    fn peek(&self) -> Option<&T> { self.head() }
+}
+
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
         let mut head = self.head.take();
@@ -0,0 +1,61 @@
+// One vocabulary for the lists, so code written against a `Stack`, `Queue`
+// or `Deque` can switch lists by changing a type parameter. `silly2` is left
+// out: its frames live on the call stack and can't be pushed through `&mut`.
+
+use std::ops::Deref;
+
+/// Last in, first out.
+pub trait Stack<T> {
+    /// What `peek` lends out: `&T` for most lists, a `Ref` for `fourth`.
+    type Peek<'a>: Deref<Target = T> where Self: 'a;
+
+    fn push(&mut self, elem: T);
+    fn pop(&mut self) -> Option<T>;
+    /// The element `pop` would return.
+    fn peek(&self) -> Option<Self::Peek<'_>>;
+}
+
+/// First in, first out: in at the back, out at the front.
+pub trait Queue<T> {
+    /// See [`Stack::Peek`].
+    type Peek<'a>: Deref<Target = T> where Self: 'a;
+
+    fn push_back(&mut self, elem: T);
+    fn pop_front(&mut self) -> Option<T>;
+    fn peek_front(&self) -> Option<Self::Peek<'_>>;
+}
+
+/// A queue that works from both ends.
+pub trait Deque<T>: Queue<T> {
+    fn push_front(&mut self, elem: T);
+    fn pop_back(&mut self) -> Option<T>;
+    fn peek_back(&self) -> Option<Self::Peek<'_>>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::Stack;
+    use {silly1, third};
+
+    #[test]
+    fn third_pop_leaves_shared_lists_alone() {
+        let mut list = third::List::new().prepend(1).prepend(2);
+        let shared = list.tail();
+
+        assert_eq!(Stack::pop(&mut list), Some(2));
+        assert_eq!(Stack::pop(&mut list), Some(1));
+        assert_eq!(Stack::pop(&mut list), None);
+        assert_eq!(shared.head(), Some(&1));
+    }
+
+    #[test]
+    fn silly1_stacks_on_the_left() {
+        let mut list = silly1::List::new();
+        list.push_right(9);
+        Stack::push(&mut list, 1);
+        assert_eq!(list.peek_left(), Some(&1));
+        assert_eq!(Stack::pop(&mut list), Some(1));
+        assert_eq!(Stack::pop(&mut list), None);
+        assert_eq!(list.peek_right(), Some(&9));
+    }
+}
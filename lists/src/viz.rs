@@ -0,0 +1,81 @@
+// Shared plumbing for the to_dot() methods. Every node is named after its
+// address, so lists that share nodes draw them once and the picture shows the
+// real pointer structure. Paste the output into `dot -Tsvg` or any Graphviz
+// viewer. It's pub so the code crate can draw its lists with it too, but it
+// isn't part of the API.
+use std::collections::HashSet;
+use std::fmt::{Debug, Write};
+
+pub struct Dot {
+    out: String,
+    seen: HashSet<String>,
+}
+
+// Graphviz name for whatever lives at `ptr`.
+pub fn id<T>(ptr: *const T) -> String {
+    format!("{:p}", ptr)
+}
+
+// Debug output of an element, made safe to put in a record label.
+pub fn elem<T: Debug>(elem: &T) -> String {
+    let mut out = String::new();
+    for c in format!("{:?}", elem).chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '"' | '\\' | '{' | '}' | '|' | '<' | '>' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+impl Dot {
+    pub fn new(name: &str) -> Self {
+        let mut out = String::new();
+        writeln!(out, "digraph \"{}\" {{", name).unwrap();
+        out.push_str("    rankdir=LR;\n");
+        out.push_str("    node [shape=record, fontname=monospace];\n");
+        Dot {
+            out,
+            seen: HashSet::new(),
+        }
+    }
+
+    // The list struct itself, which only holds pointers into the nodes.
+    pub fn handle(&mut self, id: &str, label: &str) {
+        writeln!(self.out, "    \"{}\" [shape=plaintext, label=\"{}\"];", id, label).unwrap();
+    }
+
+    // A node showing its address and then `fields`. Returns false if the node
+    // was already drawn, which is where walking a shared tail can stop.
+    pub fn node(&mut self, id: &str, fields: &[String]) -> bool {
+        if !self.seen.insert(id.to_string()) {
+            return false;
+        }
+        let mut label = id.to_string();
+        for field in fields {
+            label.push('|');
+            label.push_str(field);
+        }
+        writeln!(self.out, "    \"{}\" [label=\"{{{}}}\"];", id, label).unwrap();
+        true
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, label: &str) {
+        writeln!(self.out, "    \"{}\" -> \"{}\" [label=\"{}\"];", from, to, label).unwrap();
+    }
+
+    // Links pointing back towards the front, drawn dashed so they don't pull
+    // the layout around.
+    pub fn back_edge(&mut self, from: &str, to: &str, label: &str) {
+        writeln!(self.out, "    \"{}\" -> \"{}\" [label=\"{}\", style=dashed, constraint=false];", from, to, label).unwrap();
+    }
+
+    pub fn finish(mut self) -> String {
+        self.out.push_str("}\n");
+        self.out
+    }
+}